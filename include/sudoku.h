@@ -0,0 +1,40 @@
+/* C API of the sudoku solver, built with `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+ *
+ * Every grid is 81 bytes in row-major order with 0 for empty cells.
+ * The caller allocates and owns every buffer, the library never keeps a pointer after a call returns.
+ * Every function returns one of the status codes below, the same numbers as the exit codes of the command line tool. */
+
+#ifndef SUDOKU_H
+#define SUDOKU_H
+
+#include <stdint.h>
+
+/* the call succeeded */
+#define SUDOKU_OK 0
+/* the puzzle has no solution, also when its givens already break the rules */
+#define SUDOKU_UNSOLVABLE 1
+/* a pointer was null or the grid has a value above 9 */
+#define SUDOKU_INVALID_INPUT 2
+/* the solver panicked, which is a bug in the solver rather than in the input */
+#define SUDOKU_INTERNAL_ERROR 3
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Writes the solution of grid to out, which is left untouched unless SUDOKU_OK is returned.
+ * grid and out may be the same buffer. */
+int32_t sudoku_solve(const uint8_t grid[81], uint8_t out[81]);
+
+/* Writes to count how many solutions grid has, counting stops at limit.
+ * A grid whose givens break the rules has 0 solutions. */
+int32_t sudoku_count_solutions(const uint8_t grid[81], uint32_t limit, uint32_t *count);
+
+/* Writes to conflicts how many pairs of givens share a row, column or region and a digit. */
+int32_t sudoku_validate(const uint8_t grid[81], uint32_t *conflicts);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
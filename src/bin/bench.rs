@@ -0,0 +1,158 @@
+//! Solves every puzzle of a file with one puzzle per line, as read by `SdkParser`, and prints statistics of the whole run.
+//! `cargo run --release --bin bench -- puzzles.sdk`, add `--strategy <engine>` to compare the engines of `--strategy`
+//! of the CLI, e.g. `--strategy bitmask` against the default `wfc`, and `--lcv` to compare guessing the least
+//! constraining value first against the collapse order, see `ValueOrder`.
+//! `--compare` solves the puzzles with the `bitmask` baseline and the `wfc` engine one after the other and reports both,
+//! `puzzles/hard.sdk` holds a set of well known hard puzzles for it.
+//! `--uniqueness` instead times the check clue removal runs over and over while generating: every given of every
+//! puzzle is removed in turn and `has_unique_solution` decides whether the rest still has a single solution
+
+use sudoku_solver_cli::exit_code;
+use sudoku_solver_cli::sdk::SdkParser;
+use sudoku_solver_cli::sudoku::{Board, SolveConfig, Solver, SudokuSolver, ValueOrder};
+
+use std::io::BufReader;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.first() else {
+        eprintln!("Usage: bench <puzzle file> [--strategy <engine>] [--compare] [--lcv] [--uniqueness]");
+        std::process::exit(exit_code::INVALID_INPUT);
+    };
+    let strategy = args.iter().position(|arg| arg == "--strategy").and_then(|i| args.get(i + 1)).map_or("wfc", String::as_str);
+    let Some(mut solver) = <dyn Solver>::by_name(strategy) else {
+        let names: Vec<&str> = <dyn Solver>::all().into_iter().map(|(name, _)| name).collect();
+        eprintln!("Error: unknown strategy {}, expected one of {}", strategy, names.join(", "));
+        std::process::exit(exit_code::INVALID_INPUT);
+    };
+    let value_order = if args.iter().any(|arg| arg == "--lcv") { ValueOrder::LeastConstraining } else { ValueOrder::CollapseOrder };
+    let config = SolveConfig { value_order, ..SolveConfig::default() };
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Error: {}: {}", path, e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    };
+
+    if args.iter().any(|arg| arg == "--uniqueness") {
+        bench_uniqueness(BufReader::new(file));
+        return;
+    }
+
+    let mut puzzles = Vec::new();
+    let mut unreadable = 0;
+    for (i, puzzle) in SdkParser::read(BufReader::new(file)).enumerate() {
+        match puzzle {
+            Ok((sudoku, _)) => puzzles.push(sudoku.into_board()),
+            Err(e) => {
+                eprintln!("Puzzle {}: {}", i + 1, e);
+                unreadable += 1;
+            }
+        }
+    }
+    println!("Value order: {}", value_order);
+    println!("Unreadable: {}", unreadable);
+
+    if args.iter().any(|arg| arg == "--compare") {
+        let mut totals = Vec::new();
+        for engine in ["wfc", "bitmask"] {
+            let mut solver = <dyn Solver>::by_name(engine).expect("both engines are always available");
+            println!();
+            println!("Strategy: {}", engine);
+            let run = Run::of(solver.as_mut(), &puzzles, &config);
+            run.print();
+            totals.push(run.times.iter().sum::<Duration>());
+        }
+        if let [wfc, bitmask] = totals[..] {
+            if !bitmask.is_zero() {
+                println!();
+                println!("bitmask is {:.1}x as fast as wfc", wfc.as_secs_f64() / bitmask.as_secs_f64());
+            }
+        }
+        return;
+    }
+    println!("Strategy: {}", strategy);
+    Run::of(solver.as_mut(), &puzzles, &config).print();
+}
+
+/// how solving a set of puzzles went
+struct Run {
+    /// of every solved puzzle from the fastest to the slowest
+    times: Vec<Duration>,
+    failed: usize,
+    guesses: usize,
+    backtracks: usize
+}
+
+impl Run {
+    fn of(solver: &mut dyn Solver, puzzles: &[Board], config: &SolveConfig) -> Run {
+        let mut run = Run { times: Vec::new(), failed: 0, guesses: 0, backtracks: 0 };
+        for (i, puzzle) in puzzles.iter().enumerate() {
+            let mut board = *puzzle;
+            match solver.solve(&mut board, config) {
+                Ok(report) => {
+                    run.times.push(report.elapsed);
+                    run.guesses += report.guesses;
+                    run.backtracks += report.backtracks;
+                },
+                Err(e) => {
+                    eprintln!("Puzzle {}: {}", i + 1, e);
+                    run.failed += 1;
+                }
+            }
+        }
+        run.times.sort();
+        run
+    }
+
+    fn print(&self) {
+        println!("Solved: {}", self.times.len());
+        println!("Failed: {}", self.failed);
+        if let (Some(min), Some(max)) = (self.times.first(), self.times.last()) {
+            println!("Total time: {:?}", self.times.iter().sum::<Duration>());
+            println!("Min time: {:?}", min);
+            println!("Median time: {:?}", self.times[self.times.len() / 2]);
+            println!("Max time: {:?}", max);
+        }
+        println!("Total guesses: {}", self.guesses);
+        println!("Total backtracks: {}", self.backtracks);
+    }
+}
+
+/// times `has_unique_solution` on every puzzle with each of its givens removed
+fn bench_uniqueness(reader: impl std::io::BufRead) {
+    let mut checks = 0;
+    let mut unique = 0;
+    let mut total = Duration::ZERO;
+    let mut slowest = Duration::ZERO;
+    for (i, puzzle) in SdkParser::read(reader).enumerate() {
+        let sudoku = match puzzle {
+            Ok((sudoku, _)) => sudoku,
+            Err(e) => {
+                eprintln!("Puzzle {}: {}", i + 1, e);
+                continue;
+            }
+        };
+        let grid = sudoku.as_array();
+        for (point, _) in sudoku.givens() {
+            let mut removed = grid;
+            removed[point.y][point.x] = 0;
+            let Ok(removed) = SudokuSolver::new(removed) else { continue };
+            let start = Instant::now();
+            unique += removed.has_unique_solution() as usize;
+            let elapsed = start.elapsed();
+            checks += 1;
+            total += elapsed;
+            slowest = slowest.max(elapsed);
+        }
+    }
+    println!("Uniqueness checks: {}", checks);
+    println!("Still unique: {}", unique);
+    println!("Total time: {:?}", total);
+    if checks > 0 {
+        println!("Mean time: {:?}", total / checks as u32);
+        println!("Max time: {:?}", slowest);
+    }
+}
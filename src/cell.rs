@@ -1,14 +1,20 @@
-use std::collections::HashSet;
+use alloc::collections::BTreeSet;
 
 #[derive(Debug, Clone)]
 pub struct UncollapsedCell {
-    possible_values: HashSet<u8>
+    possible_values: BTreeSet<u8>
 }
 
 impl UncollapsedCell {
-    fn new() -> Self {
+    fn new(size: u8) -> Self {
         UncollapsedCell {
-            possible_values: HashSet::from([1,2,3,4,5,6,7,8,9])
+            possible_values: (1..=size).collect()
+        }
+    }
+
+    fn with_candidates(values: &[u8]) -> Self {
+        UncollapsedCell {
+            possible_values: values.iter().copied().collect()
         }
     }
 
@@ -25,8 +31,27 @@ impl UncollapsedCell {
         }
     }
 
-    fn collapse(self) -> Cell {
-        Cell::Collapsed(self.possible_values.into_iter().next().unwrap())
+    fn contains(&self, value: u8) -> bool {
+        self.possible_values.contains(&value)
+    }
+
+    fn candidates(&self) -> impl Iterator<Item = u8> + '_ {
+        self.possible_values.iter().copied()
+    }
+
+    fn single_value(&self) -> Option<u8> {
+        if self.possible_values.len() == 1 {
+            self.possible_values.iter().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// collapses to the first candidate in `order`, or to the smallest one if `order` has none of them,
+    /// so that solving is deterministic
+    fn collapse(self, order: &[u8]) -> Cell {
+        let preferred = order.iter().copied().find(|value| self.possible_values.contains(value));
+        Cell::Collapsed(preferred.unwrap_or_else(|| self.possible_values.into_iter().min().unwrap()))
     }
 }
 
@@ -37,8 +62,15 @@ pub enum Cell {
 }
 
 impl Cell {
-    pub fn new_empty() -> Self {
-        Cell::Uncollapsed(UncollapsedCell::new())
+    /// `size` is the side length of the board, which is also the highest digit
+    pub fn new_empty(size: u8) -> Self {
+        Cell::Uncollapsed(UncollapsedCell::new(size))
+    }
+
+    /// an uncollapsed cell with exactly `values` as its candidates, which must not be empty
+    pub fn new_with_candidates(values: &[u8]) -> Self {
+        debug_assert!(!values.is_empty(), "a cell needs at least one candidate");
+        Cell::Uncollapsed(UncollapsedCell::with_candidates(values))
     }
 
     pub fn new_filled(value: u8) -> Self {
@@ -52,11 +84,12 @@ impl Cell {
         }
     }
 
-    /// returns the value that the cell was collapsed to
-    pub fn collapse(&mut self) -> Cell{
+    /// collapses to the first candidate in `order`, see `UncollapsedCell::collapse`,
+    /// and returns the cell as it was before without the value it was collapsed to
+    pub fn collapse(&mut self, order: &[u8]) -> Cell{
         let mut cell = self.clone();
         *self = match self {
-            Cell::Uncollapsed(c) => c.clone().collapse(),
+            Cell::Uncollapsed(c) => c.clone().collapse(order),
             Cell::Collapsed(_) => panic!("collapse called on a collapsed cell"),
         };
         if cell.get_entropy() > 1 {
@@ -66,6 +99,44 @@ impl Cell {
         cell
     }
 
+    pub fn is_collapsed(&self) -> bool {
+        matches!(self, Cell::Collapsed(_))
+    }
+
+    /// returns the value of a collapsed cell
+    pub fn value(&self) -> Option<u8> {
+        match self {
+            Cell::Collapsed(v) => Some(*v),
+            Cell::Uncollapsed(_) => None
+        }
+    }
+
+    /// true if the cell is collapsed to `value` or still has it as a candidate
+    pub fn contains(&self, value: u8) -> bool {
+        match self {
+            Cell::Uncollapsed(c) => c.contains(value),
+            Cell::Collapsed(v) => *v == value
+        }
+    }
+
+    /// The digits the cell can still be from the smallest up, for a collapsed cell that is only its value,
+    /// so every digit it yields is one `contains` is true for
+    pub fn candidates(&self) -> impl Iterator<Item = u8> + '_ {
+        let (uncollapsed, collapsed) = match self {
+            Cell::Uncollapsed(c) => (Some(c.candidates()), None),
+            Cell::Collapsed(v) => (None, Some(*v))
+        };
+        uncollapsed.into_iter().flatten().chain(collapsed)
+    }
+
+    /// returns the only remaining candidate of an uncollapsed cell
+    pub fn single_candidate(&self) -> Option<u8> {
+        match self {
+            Cell::Uncollapsed(c) => c.single_value(),
+            Cell::Collapsed(_) => None
+        }
+    }
+
     pub fn remove(&mut self, value: u8) -> Result<(), ()> {
         match self {
             Cell::Uncollapsed(c) => c.remove(value),
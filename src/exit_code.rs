@@ -0,0 +1,37 @@
+//! The exit codes of the command line tool, the `SUDOKU_*` status codes of `ffi` are the same numbers.
+//! Messages go to stderr, so scripts can tell what went wrong from the code alone
+//!
+//! ```
+//! use sudoku_solver_cli::exit_code;
+//! use sudoku_solver_cli::sudoku::{SudokuError, SudokuSolver};
+//!
+//! let mut grid = [[0; 9]; 9];
+//! grid[0][0] = 1;
+//! grid[0][1] = 1;
+//! let error = SudokuError::from(SudokuSolver::new(grid).unwrap_err());
+//! assert_eq!(exit_code::for_error(&error), exit_code::UNSOLVABLE);
+//! assert_eq!(exit_code::for_error(&SudokuError::Timeout), exit_code::LIMIT_REACHED);
+//! ```
+
+use crate::sudoku::SudokuError;
+
+/// the puzzle was solved, or checked without finding a conflict
+pub const SUCCESS: i32 = 0;
+/// the puzzle has no solution, also when its givens already break the rules or `check` found a conflict
+pub const UNSOLVABLE: i32 = 1;
+/// the puzzle could not be read or parsed, or an option has a value that is not one of its choices
+pub const INVALID_INPUT: i32 = 2;
+/// the solver panicked or the server failed, which is not caused by the puzzle
+pub const INTERNAL_ERROR: i32 = 3;
+/// the solve gave up on a timeout, a limit or being cancelled before it found out whether there is a solution
+pub const LIMIT_REACHED: i32 = 4;
+
+/// the exit code for a solve that failed with `error`
+pub fn for_error(error: &SudokuError) -> i32 {
+    match error {
+        SudokuError::InitialContradiction(_) | SudokuError::Unsolvable | SudokuError::NotUnique => UNSOLVABLE,
+        SudokuError::InvalidDimensions | SudokuError::OutOfBounds | SudokuError::InvalidSolution => INVALID_INPUT,
+        SudokuError::Timeout | SudokuError::BacktrackLimitReached | SudokuError::Cancelled
+        | SudokuError::DepthLimitExceeded => LIMIT_REACHED
+    }
+}
@@ -0,0 +1,124 @@
+//! A C API for the solver, behind the `ffi` feature. Build it as a shared library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib` and include `include/sudoku.h`.
+//!
+//! Every grid is 81 bytes in row-major order with `0` for empty cells. The caller allocates and owns
+//! every buffer, nothing is allocated for the caller or kept after a call returns.
+//! Every function returns one of the `SUDOKU_*` status codes, the same numbers as the exit codes of the command line tool
+//! in `exit_code`, and never unwinds into C,
+//! a panic is caught and reported as `SUDOKU_INTERNAL_ERROR`
+//!
+//! ```
+//! use sudoku_solver_cli::ffi::*;
+//!
+//! let puzzle = b"000000080680470020019508647060900004342680000190050830000720403006005010003891500";
+//! let grid: Vec<u8> = puzzle.iter().map(|c| c - b'0').collect();
+//! let mut solution = [0u8; 81];
+//! assert_eq!(unsafe { sudoku_solve(grid.as_ptr(), solution.as_mut_ptr()) }, SUDOKU_OK);
+//! assert_eq!(solution[..9], [7, 3, 4, 1, 6, 2, 9, 8, 5]);
+//!
+//! let mut count = 0;
+//! assert_eq!(unsafe { sudoku_count_solutions(grid.as_ptr(), 2, &mut count) }, SUDOKU_OK);
+//! assert_eq!(count, 1);
+//!
+//! let mut broken = grid.clone();
+//! broken[0] = 8;
+//! let mut conflicts = 0;
+//! assert_eq!(unsafe { sudoku_validate(broken.as_ptr(), &mut conflicts) }, SUDOKU_OK);
+//! assert_eq!(conflicts, 2);
+//! assert_eq!(unsafe { sudoku_solve(broken.as_ptr(), solution.as_mut_ptr()) }, SUDOKU_UNSOLVABLE);
+//!
+//! broken[0] = 10;
+//! assert_eq!(unsafe { sudoku_validate(broken.as_ptr(), &mut conflicts) }, SUDOKU_INVALID_INPUT);
+//! assert_eq!(unsafe { sudoku_solve(std::ptr::null(), solution.as_mut_ptr()) }, SUDOKU_INVALID_INPUT);
+//! ```
+
+use crate::exit_code;
+use crate::point::Point;
+use crate::sudoku::SudokuSolver;
+
+use std::panic::{self, AssertUnwindSafe};
+
+/// the call succeeded
+pub const SUDOKU_OK: i32 = exit_code::SUCCESS;
+/// the puzzle has no solution, also when its givens already break the rules
+pub const SUDOKU_UNSOLVABLE: i32 = exit_code::UNSOLVABLE;
+/// a pointer was null or the grid has a value above 9
+pub const SUDOKU_INVALID_INPUT: i32 = exit_code::INVALID_INPUT;
+/// the solver panicked, which is a bug in the solver rather than in the input
+pub const SUDOKU_INTERNAL_ERROR: i32 = exit_code::INTERNAL_ERROR;
+
+/// Writes the solution of `grid` to `out`, which is left untouched unless `SUDOKU_OK` is returned.
+///
+/// # Safety
+/// `grid` must point to 81 readable bytes and `out` to 81 writable bytes, they may be the same buffer
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_solve(grid: *const u8, out: *mut u8) -> i32 {
+    guarded(|| {
+        let Some(starting_state) = read_grid(grid) else { return SUDOKU_INVALID_INPUT };
+        if out.is_null() {
+            return SUDOKU_INVALID_INPUT;
+        }
+        match SudokuSolver::new(starting_state).map_err(|_| ()).and_then(|sudoku| sudoku.into_solution().map_err(|_| ())) {
+            Ok(solution) => {
+                let out = std::slice::from_raw_parts_mut(out, 81);
+                out.copy_from_slice(solution.as_flattened());
+                SUDOKU_OK
+            },
+            Err(_) => SUDOKU_UNSOLVABLE
+        }
+    })
+}
+
+/// Writes to `count` how many solutions `grid` has, counting stops at `limit`.
+/// A grid whose givens break the rules has 0 solutions
+///
+/// # Safety
+/// `grid` must point to 81 readable bytes and `count` to a writable `uint32_t`
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_count_solutions(grid: *const u8, limit: u32, count: *mut u32) -> i32 {
+    guarded(|| {
+        let Some(starting_state) = read_grid(grid) else { return SUDOKU_INVALID_INPUT };
+        if count.is_null() {
+            return SUDOKU_INVALID_INPUT;
+        }
+        let solutions = SudokuSolver::new(starting_state).map_or(0, |sudoku| sudoku.count_solutions(limit as usize));
+        *count = solutions as u32;
+        SUDOKU_OK
+    })
+}
+
+/// Writes to `conflicts` how many pairs of givens in `grid` share a row, column or region and a digit,
+/// see `SudokuSolver::validate`
+///
+/// # Safety
+/// `grid` must point to 81 readable bytes and `conflicts` to a writable `uint32_t`
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_validate(grid: *const u8, conflicts: *mut u32) -> i32 {
+    guarded(|| {
+        let Some(starting_state) = read_grid(grid) else { return SUDOKU_INVALID_INPUT };
+        if conflicts.is_null() {
+            return SUDOKU_INVALID_INPUT;
+        }
+        *conflicts = SudokuSolver::validate(&starting_state).len() as u32;
+        SUDOKU_OK
+    })
+}
+
+/// the grid behind `grid`, None if it is null or has a value that is not a digit
+unsafe fn read_grid(grid: *const u8) -> Option<[[u8; 9]; 9]> {
+    if grid.is_null() {
+        return None;
+    }
+    let cells = std::slice::from_raw_parts(grid, 81);
+    let mut starting_state = [[0; 9]; 9];
+    for (i, value) in cells.iter().enumerate() {
+        let point = Point::from_index(i, 9);
+        starting_state[point.y][point.x] = *value;
+    }
+    cells.iter().all(|value| *value <= 9).then_some(starting_state)
+}
+
+/// runs `f` so that a panic turns into `SUDOKU_INTERNAL_ERROR` instead of unwinding into C
+fn guarded(f: impl FnOnce() -> i32) -> i32 {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(SUDOKU_INTERNAL_ERROR)
+}
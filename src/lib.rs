@@ -0,0 +1,42 @@
+//! A sudoku solver that collapses the cell with the fewest candidates first and backtracks on contradictions.
+//!
+//! ```
+//! use sudoku_solver_cli::sudoku::{SudokuError, SudokuSolver};
+//!
+//! # fn main() -> Result<(), SudokuError> {
+//! let grid = [
+//!     [0, 0, 0, 0, 0, 0, 0, 8, 0],
+//!     [6, 8, 0, 4, 7, 0, 0, 2, 0],
+//!     [0, 1, 9, 5, 0, 8, 6, 4, 7],
+//!     [0, 6, 0, 9, 0, 0, 0, 0, 4],
+//!     [3, 4, 2, 6, 8, 0, 0, 0, 0],
+//!     [1, 9, 0, 0, 5, 0, 8, 3, 0],
+//!     [0, 0, 0, 7, 2, 0, 4, 0, 3],
+//!     [0, 0, 6, 0, 0, 5, 0, 1, 0],
+//!     [0, 0, 3, 8, 9, 1, 5, 0, 0]
+//! ];
+//! let solved = SudokuSolver::new(grid)?.into_solution()?;
+//! assert_eq!(solved[0], [7, 3, 4, 1, 6, 2, 9, 8, 5]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Everything that needs an operating system, timeouts, threads and reading files, is behind the default `std` feature.
+//! Without it the crate is `no_std` and only needs `alloc`
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod sudoku;
+mod cell;
+pub mod point;
+pub mod parse;
+pub mod exit_code;
+#[cfg(feature = "std")]
+pub mod sdk;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod ss;
@@ -1,10 +1,173 @@
-mod sudoku;
-mod cell;
-mod point;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "serve")]
+mod serve;
 
-use crate::sudoku::SudokuSolver;
+use sudoku_solver_cli::{exit_code, parse};
+use sudoku_solver_cli::sudoku::{Board, LogicalOutcome, SolveConfig, Solver, SudokuError, SudokuSolver, Technique, TechniquePipeline, TechniqueSet, TraceEvent};
+
+use std::io::Read;
+
+/// reads the puzzle from the file at `path`, or from stdin when `path` is `-`
+fn read_puzzle(path: &str) -> Result<[[u8; 9]; 9], String> {
+    let mut input = String::new();
+    if path == "-" {
+        std::io::stdin().read_to_string(&mut input).map_err(|e| e.to_string())?;
+    } else {
+        input = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    let normalized = parse::validate_and_normalize(&input).map_err(|e| e.to_string())?;
+    for warning in normalized.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(normalized.grid)
+}
+
+/// prints `message` to stderr and exits with `code`, one of `exit_code`
+fn fail(code: i32, message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(code)
+}
+
+fn print_repair(starting_state: [[u8; 9]; 9]) {
+    let removals: Vec<String> = SudokuSolver::suggest_clue_removals(starting_state).iter()
+        .map(|point| format!("{} ({})", point.rc(), starting_state[point.y][point.x]))
+        .collect();
+    if !removals.is_empty() {
+        println!("Removing these clues makes the sudoku solvable: {}", removals.join(", "));
+    }
+}
+
+/// the 81 cells in row-major order with `.` for empty cells
+fn to_flat(grid: [[u8; 9]; 9]) -> String {
+    grid.iter().flatten().map(|value| if *value == 0 { '.' } else { char::from(b'0' + value) }).collect()
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// The techniques left by `--techniques`, a comma separated list in the order to try them, and by any number
+/// of `--disable-technique`, every technique when neither is given
+fn technique_pipeline(args: &[String]) -> Result<TechniquePipeline, String> {
+    let technique = |name: &str| Technique::by_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Technique::ALL.iter().map(Technique::name).collect();
+        format!("unknown technique {}, expected one of {}", name, names.join(", "))
+    });
+    let values_of = |flag: &str| args.iter().enumerate().filter(|(_, arg)| *arg == flag)
+        .filter_map(|(i, _)| args.get(i + 1))
+        .collect::<Vec<_>>();
+
+    let chosen = values_of("--techniques");
+    let disabled = values_of("--disable-technique");
+    let mut pipeline = match chosen.last() {
+        Some(list) => TechniquePipeline::new(list.split(',').map(|name| technique(name.trim())).collect::<Result<Vec<_>, _>>()?),
+        None => TechniquePipeline::default()
+    };
+    for name in disabled {
+        pipeline = pipeline.without(technique(name)?);
+    }
+    Ok(pipeline)
+}
+
+/// Fills in everything the techniques of `pipeline` force without guessing, for `--no-guess`
+fn solve_without_guessing(starting_state: [[u8; 9]; 9], pipeline: &TechniquePipeline) {
+    let mut sudoku = SudokuSolver::new(starting_state).unwrap_or_else(|e| fail(exit_code::UNSOLVABLE, &e.to_string()));
+    let techniques: TechniqueSet = pipeline.techniques().iter().copied().collect();
+    match sudoku.solve_logical_with(techniques) {
+        LogicalOutcome::Solved => println!("Solved without guessing\n{}", sudoku),
+        LogicalOutcome::Stalled { .. } => {
+            println!("Stuck without guessing, the empty cells show how many candidates they have left\n{}", sudoku.display_candidate_counts());
+        },
+        LogicalOutcome::Unsolvable(contradiction) => fail(exit_code::UNSOLVABLE, &contradiction.to_string())
+    }
+}
+
+/// Solves the puzzle printing newline delimited JSON events instead of the human readable output
+fn solve_with_json_log(starting_state: [[u8; 9]; 9], config: &SolveConfig) {
+    println!("{{\"event\":\"start\",\"puzzle\":{}}}", json_string(&to_flat(starting_state)));
+    let report = SudokuSolver::new(starting_state).map_err(SudokuError::from).and_then(|mut sudoku| {
+        let config = SolveConfig { trace: true, ..config.clone() };
+        let report = sudoku.solve_with_report(&config)?;
+        Ok((sudoku, report))
+    });
+    let (sudoku, report) = match report {
+        Ok(solved) => solved,
+        Err(e) => {
+            println!("{{\"event\":\"error\",\"message\":{}}}", json_string(&e.to_string()));
+            std::process::exit(exit_code::for_error(&e));
+        }
+    };
+
+    for event in &report.trace {
+        match event {
+            TraceEvent::Collapse { cell, value, .. } => {
+                println!("{{\"event\":\"collapse\",\"cell\":\"R{}C{}\",\"value\":{}}}", cell.y + 1, cell.x + 1, value);
+            },
+            TraceEvent::Backtrack { depth, contradiction: Some(contradiction) } => {
+                println!("{{\"event\":\"backtrack\",\"depth\":{},\"contradiction\":{}}}", depth, json_string(&contradiction.to_string()));
+            },
+            TraceEvent::Backtrack { depth, contradiction: None } => println!("{{\"event\":\"backtrack\",\"depth\":{}}}", depth),
+            TraceEvent::Deduction(deduction) => {
+                println!("{{\"event\":\"deduction\",\"technique\":{},\"reason\":{}}}",
+                         json_string(deduction.technique().name()), json_string(&deduction.to_string()));
+            },
+            TraceEvent::Solved => {}
+        }
+    }
+    println!("{{\"event\":\"solved\",\"solution\":{},\"backtracks\":{},\"duration_ms\":{}}}",
+             json_string(&to_flat(sudoku.as_array())), report.backtracks, report.elapsed.as_millis());
+}
+
+/// `--serve` on `--port`, 8080 by default, with the metrics on `--metrics-port` if it is given
+#[cfg(feature = "serve")]
+fn run_server(args: &[String]) {
+    let port_of = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|i| args.get(i + 1))
+        .map(|port| port.parse::<u16>().map_err(|e| format!("invalid {}: {}", flag, e)))
+        .transpose();
+    let (port, metrics_port) = port_of("--port").and_then(|port| Ok((port.unwrap_or(8080), port_of("--metrics-port")?)))
+        .unwrap_or_else(|e| fail(exit_code::INVALID_INPUT, &e));
+    if cfg!(not(feature = "metrics")) && metrics_port.is_some() {
+        fail(exit_code::INVALID_INPUT, "--metrics-port needs the metrics feature, build with `--features metrics`");
+    }
+    if let Err(e) = serve::serve(port, metrics_port) {
+        fail(exit_code::INTERNAL_ERROR, &e.to_string());
+    }
+}
+
+#[cfg(not(feature = "serve"))]
+fn run_server(_: &[String]) {
+    fail(exit_code::INVALID_INPUT, "--serve needs the serve feature, build with `--features serve`");
+}
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let show_stats = args.iter().any(|arg| arg == "--stats");
+    let suggest_repair = args.iter().any(|arg| arg == "--repair");
+    let log_json = args.iter().any(|arg| arg == "--log-json");
+    let no_guess = args.iter().any(|arg| arg == "--no-guess");
+    let check = args.first().is_some_and(|arg| arg == "check");
+    let takes_value = |arg: &str| ["--port", "--metrics-port", "--strategy", "--techniques", "--disable-technique"].contains(&arg);
+    let puzzle_path = args.iter().enumerate().skip(if check { 1 } else { 0 })
+        .find(|(i, arg)| !arg.starts_with("--") && (*i == 0 || !takes_value(&args[i - 1])))
+        .map(|(_, arg)| arg);
+
+    if args.iter().any(|arg| arg == "--serve") {
+        run_server(&args);
+        return;
+    }
+
     // let mut sudoku = SudokuSolver::new([
     //                           [1, 0, 0, 0, 0, 0, 0, 0, 0],
     //                           [0, 0, 0, 0, 0, 0, 0, 0, 0],
@@ -16,29 +179,79 @@ fn main() {
     //                           [0, 6, 0, 0, 0, 0, 0, 0, 0],
     //                           [0, 5, 0, 0, 0, 0, 0, 0, 0]
     // ]);
-    let sudoku = SudokuSolver::new([
-        [0, 0, 0, 0, 0, 0, 0, 8, 0],
-        [6, 8, 0, 4, 7, 0, 0, 2, 0],
-        [0, 1, 9, 5, 0, 8, 6, 4, 7],
-        [0, 6, 0, 9, 0, 0, 0, 0, 4],
-        [3, 4, 2, 6, 8, 0, 0, 0, 0],
-        [1, 9, 0, 0, 5, 0, 8, 3, 0],
-        [0, 0, 0, 7, 2, 0, 4, 0, 3],
-        [0, 0, 6, 0, 0, 5, 0, 1, 0],
-        [0, 0, 3, 8, 9, 1, 5, 0, 0]
-    ]);
-    if let Err(e) = sudoku {
-        println!("Error: {}", e);
+    let starting_state = match puzzle_path {
+        Some(path) => match read_puzzle(path) {
+            Ok(starting_state) => starting_state,
+            Err(e) if log_json => {
+                println!("{{\"event\":\"error\",\"message\":{}}}", json_string(&e));
+                std::process::exit(exit_code::INVALID_INPUT);
+            },
+            Err(e) => fail(exit_code::INVALID_INPUT, &e)
+        },
+        None => [
+            [0, 0, 0, 0, 0, 0, 0, 8, 0],
+            [6, 8, 0, 4, 7, 0, 0, 2, 0],
+            [0, 1, 9, 5, 0, 8, 6, 4, 7],
+            [0, 6, 0, 9, 0, 0, 0, 0, 4],
+            [3, 4, 2, 6, 8, 0, 0, 0, 0],
+            [1, 9, 0, 0, 5, 0, 8, 3, 0],
+            [0, 0, 0, 7, 2, 0, 4, 0, 3],
+            [0, 0, 6, 0, 0, 5, 0, 1, 0],
+            [0, 0, 3, 8, 9, 1, 5, 0, 0]
+        ]
+    };
+    if check {
+        let conflicts = SudokuSolver::validate(&starting_state);
+        if conflicts.is_empty() {
+            println!("No conflicts found");
+        }
+        for conflict in &conflicts {
+            println!("{}", conflict);
+        }
+        if !conflicts.is_empty() {
+            println!();
+            print!("{}", Board::new(starting_state).display_conflicts());
+            std::process::exit(exit_code::UNSOLVABLE);
+        }
         return;
     }
-    let mut sudoku = sudoku.unwrap();
-
-    let is_solve_successful = sudoku.solve();
-    if let Err(e) = is_solve_successful {
-        println!("Error: {}", e);
+    let pipeline = technique_pipeline(&args).unwrap_or_else(|e| fail(exit_code::INVALID_INPUT, &e));
+    let config = SolveConfig { pipeline, ..SolveConfig::default() };
+    if log_json {
+        solve_with_json_log(starting_state, &config);
+        return;
+    }
+    if no_guess {
+        solve_without_guessing(starting_state, &config.pipeline);
         return;
     }
 
-    println!("Is sudoku correct: {}", sudoku.check_if_correct());
+    let strategy = args.iter().position(|arg| arg == "--strategy").and_then(|i| args.get(i + 1)).map_or("wfc", String::as_str);
+    let Some(mut solver) = <dyn Solver>::by_name(strategy) else {
+        let names: Vec<&str> = <dyn Solver>::all().into_iter().map(|(name, _)| name).collect();
+        fail(exit_code::INVALID_INPUT, &format!("unknown strategy {}, expected one of {}", strategy, names.join(", ")));
+    };
+    // the other engines do not apply techniques, so the flags would be ignored without a word
+    if strategy != "wfc" && args.iter().any(|arg| arg == "--techniques" || arg == "--disable-technique") {
+        fail(exit_code::INVALID_INPUT, &format!("--techniques and --disable-technique only apply to --strategy wfc, not {}", strategy));
+    }
+
+    let mut sudoku = Board::new(starting_state);
+    let report = solver.solve(&mut sudoku, &config);
+    if let Err(e) = report {
+        eprintln!("Error: {}", e);
+        if matches!(e, SudokuError::InitialContradiction(_)) {
+            eprintln!("Run with `check` before the puzzle path to list every conflict");
+        }
+        if suggest_repair {
+            print_repair(starting_state);
+        }
+        std::process::exit(exit_code::for_error(&e));
+    }
+
+    println!("Is sudoku correct: {}", sudoku.is_complete() && sudoku.is_valid());
     println!("{}", sudoku);
+    if show_stats {
+        println!("{}", report.unwrap());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+//! Counters of the solves made through `--serve`, exported in the Prometheus text format by `--metrics-port`
+
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounterVec, Opts, Registry, TextEncoder};
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveResult {
+    Success,
+    Failure,
+    Timeout
+}
+
+impl SolveResult {
+    const ALL: [SolveResult; 3] = [SolveResult::Success, SolveResult::Failure, SolveResult::Timeout];
+
+    /// the `result` label of `sudoku_solves_total`
+    fn label(&self) -> &'static str {
+        match self {
+            SolveResult::Success => "success",
+            SolveResult::Failure => "failure",
+            SolveResult::Timeout => "timeout"
+        }
+    }
+}
+
+pub struct Metrics {
+    registry: Registry,
+    solves: IntCounterVec,
+    durations: Histogram,
+    backtracks: Histogram
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let solves = IntCounterVec::new(Opts::new("sudoku_solves_total", "Solves by result."), &["result"])
+            .expect("the counter has a valid name and label");
+        // every result is exported from the start, not just once it first happens
+        for result in SolveResult::ALL {
+            solves.with_label_values(&[result.label()]);
+        }
+        let histogram = |name: &str, help: &str, buckets: &[f64]| {
+            Histogram::with_opts(HistogramOpts::new(name, help).buckets(buckets.to_vec()))
+                .expect("the histogram has a valid name and increasing buckets")
+        };
+        let durations = histogram("sudoku_solve_duration_seconds", "Time taken by each solve.", &[0.0001, 0.001, 0.01, 0.1, 1.0, 10.0]);
+        let backtracks = histogram("sudoku_backtracks_total", "Backtracks of each finished solve.", &[0.0, 1.0, 10.0, 100.0, 1000.0, 10000.0]);
+
+        let registry = Registry::new();
+        registry.register(Box::new(solves.clone())).expect("every metric is registered once");
+        registry.register(Box::new(durations.clone())).expect("every metric is registered once");
+        registry.register(Box::new(backtracks.clone())).expect("every metric is registered once");
+        Metrics { registry, solves, durations, backtracks }
+    }
+
+    /// `backtracks` is only known for solves that got to the end
+    pub fn record(&self, result: SolveResult, duration: Duration, backtracks: Option<usize>) {
+        self.solves.with_label_values(&[result.label()]).inc();
+        self.durations.observe(duration.as_secs_f64());
+        if let Some(backtracks) = backtracks {
+            self.backtracks.observe(backtracks as f64);
+        }
+    }
+
+    pub fn to_prometheus(&self) -> String {
+        let mut output = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut output).expect("writing to a Vec cannot fail");
+        String::from_utf8(output).expect("the text format is UTF-8")
+    }
+}
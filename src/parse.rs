@@ -0,0 +1,245 @@
+//! Parsers turning text into the `[[u8; 9]; 9]` grid taken by `SudokuSolver::new`, `0` is an empty cell
+
+use crate::point::Point;
+use crate::sudoku::{Conflict, ErrorSudokuContainsAContradiction, SudokuSolver};
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidCharacter(char),
+    InvalidValue(String),
+    WrongCellCount(usize),
+    WrongRowCount(usize),
+    WrongRowLength { row: usize, length: usize },
+    /// `from_flexible` tried every format, each one with the reason it failed
+    NoFormatMatched(Vec<(&'static str, ParseError)>),
+    /// the input could not be read
+    Io(String),
+    /// every conflict between the givens of the parsed puzzle, for the readers that create a `SudokuSolver`
+    Conflicts(Vec<Conflict>),
+    /// the parsed puzzle has no direct conflicts but still breaks the rules, for the readers that create a `SudokuSolver`
+    Contradiction(ErrorSudokuContainsAContradiction)
+}
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::InvalidCharacter(c) => write!(f, "'{}' is not a digit or an empty cell", c),
+            ParseError::InvalidValue(value) => write!(f, "'{}' is not a digit or an empty cell", value),
+            ParseError::WrongCellCount(count) => write!(f, "expected 81 cells but found {}", count),
+            ParseError::WrongRowCount(count) => write!(f, "expected 9 rows but found {}", count),
+            ParseError::WrongRowLength { row, length } => write!(f, "expected 9 cells in row {} but found {}", row + 1, length),
+            ParseError::NoFormatMatched(attempts) => {
+                write!(f, "the input does not match any known format")?;
+                for (format, error) in attempts {
+                    write!(f, "\n  {}: {}", format, error)?;
+                }
+                Ok(())
+            },
+            ParseError::Io(e) => write!(f, "could not read the input: {}", e),
+            ParseError::Conflicts(conflicts) => {
+                write!(f, "the puzzle breaks the rules")?;
+                for conflict in conflicts {
+                    write!(f, "\n  {}", conflict)?;
+                }
+                Ok(())
+            },
+            ParseError::Contradiction(e) => e.fmt(f)
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// 81 characters on a single line, e.g. `530070000600195000...`, `.` or `0` for empty cells
+pub fn from_line(input: &str) -> Result<[[u8; 9]; 9], ParseError> {
+    let cells = input.trim().chars().map(parse_cell).collect::<Result<Vec<_>, _>>()?;
+    from_cells(&cells)
+}
+
+/// 9 lines of 9 characters, `.` or `0` for empty cells
+pub fn from_lines(input: &str) -> Result<[[u8; 9]; 9], ParseError> {
+    let rows = input.lines().map(str::trim).filter(|line| !line.is_empty())
+        .map(|line| line.chars().map(parse_cell).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    from_rows(&rows)
+}
+
+/// 9 rows where `|`, `+`, `-` and whitespace are separators, like the `Display` output of `to_string_with('.')`
+pub fn from_grid(input: &str) -> Result<[[u8; 9]; 9], ParseError> {
+    let rows = input.lines()
+        .map(|line| line.chars().filter(|c| !is_separator(*c)).collect::<String>())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().map(parse_cell).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    from_rows(&rows)
+}
+
+/// 81 comma separated values, line breaks count as commas and an empty value is an empty cell
+pub fn from_csv(input: &str) -> Result<[[u8; 9]; 9], ParseError> {
+    let cells = input.trim().split([',', '\n'])
+        .map(|value| match value.trim() {
+            "" => Ok(0),
+            value if value.chars().count() == 1 => parse_cell(value.chars().next().unwrap()),
+            value => Err(ParseError::InvalidValue(value.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    from_cells(&cells)
+}
+
+/// Pencilmarks for `SudokuSolver::from_candidates`, 81 groups of candidate digits separated by whitespace,
+/// e.g. `1289 5 367 ...`, with `|`, `+` and `-` allowed as additional separators
+pub fn from_pencilmarks(input: &str) -> Result<[[Vec<u8>; 9]; 9], ParseError> {
+    let cells = input.split(is_separator)
+        .filter(|group| !group.is_empty())
+        .map(|group| group.chars().map(|c| match c {
+            '1'..='9' => Ok(c as u8 - b'0'),
+            _ => Err(ParseError::InvalidCharacter(c))
+        }).collect::<Result<Vec<u8>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    if cells.len() != 81 {
+        return Err(ParseError::WrongCellCount(cells.len()));
+    }
+    let mut grid: [[Vec<u8>; 9]; 9] = Default::default();
+    for (i, cell) in cells.into_iter().enumerate() {
+        let point = Point::from_index(i, 9);
+        grid[point.y][point.x] = cell;
+    }
+    Ok(grid)
+}
+
+/// The first puzzle of a Simple Sudoku `.ss` file, see `from_simple_sudoku_file`
+pub fn from_simple_sudoku_format(input: &str) -> Result<SudokuSolver, ParseError> {
+    from_simple_sudoku_file(input)?.into_iter().next().ok_or(ParseError::WrongRowCount(0))
+}
+
+/// Every puzzle of a Simple Sudoku `.ss` file. Puzzles are separated by blank lines, each one is 9 rows
+/// with `.` for empty cells, `|` between regions and optionally lines of `-` (framed by `*` in some files) between bands
+pub fn from_simple_sudoku_file(input: &str) -> Result<Vec<SudokuSolver>, ParseError> {
+    let mut puzzles = Vec::new();
+    let mut block = String::new();
+    for line in input.lines().chain([""]) {
+        if !line.trim().is_empty() {
+            block.push_str(&line.replace('*', "-"));
+            block.push('\n');
+            continue;
+        }
+        if !block.chars().all(is_separator) {
+            puzzles.push(to_solver(from_grid(&block)?)?);
+        }
+        block.clear();
+    }
+    Ok(puzzles)
+}
+
+/// A grid parsed by `validate_and_normalize` along with what had to be cleaned up to parse it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized {
+    pub grid: [[u8; 9]; 9],
+    /// e.g. "treated '_' as empty", empty if the input was already in one of the strict formats
+    pub warnings: Vec<String>
+}
+
+/// Like `from_flexible` but also accepts a byte order mark, trailing whitespace, `_` for empty cells
+/// and, in the 9 lines format, spaces for empty cells, and reports each of these it ran into
+pub fn validate_and_normalize(input: &str) -> Result<Normalized, ParseError> {
+    let mut warnings = Vec::new();
+    let mut input = input;
+    if let Some(without_bom) = input.strip_prefix('\u{feff}') {
+        warnings.push("removed a byte order mark".to_string());
+        input = without_bom;
+    }
+    if input.contains('_') {
+        warnings.push("treated '_' as empty".to_string());
+    }
+    let input = input.replace('_', ".");
+
+    let trimmed: Vec<&str> = input.lines().map(str::trim_end).collect();
+    if trimmed.iter().zip(input.lines()).any(|(trimmed, line)| trimmed.len() != line.len()) {
+        warnings.push("removed trailing whitespace".to_string());
+    }
+    let error = match from_flexible(&trimmed.join("\n")) {
+        Ok(grid) => return Ok(Normalized { grid, warnings }),
+        Err(e) => e
+    };
+
+    // the trailing whitespace of a row can be its last empty cells, so the untrimmed lines are padded instead
+    let rows: Vec<String> = input.lines().filter(|line| !line.trim().is_empty())
+        .map(|line| format!("{:<9}", line).replace(' ', "."))
+        .collect();
+    match from_lines(&rows.join("\n")) {
+        Ok(grid) => {
+            warnings.retain(|warning| warning != "removed trailing whitespace");
+            warnings.push("treated ' ' as empty".to_string());
+            Ok(Normalized { grid, warnings })
+        },
+        Err(_) => Err(error)
+    }
+}
+
+type Parser = fn(&str) -> Result<[[u8; 9]; 9], ParseError>;
+
+/// Tries every other parser, gives the result of the first one that produces a full grid
+pub fn from_flexible(input: &str) -> Result<[[u8; 9]; 9], ParseError> {
+    let parsers: [(&'static str, Parser); 4] = [
+        ("single line", from_line),
+        ("9 lines", from_lines),
+        ("comma separated", from_csv),
+        ("grid with separators", from_grid)
+    ];
+    let mut attempts = Vec::with_capacity(parsers.len());
+    for (format, parser) in parsers {
+        match parser(input) {
+            Ok(grid) => return Ok(grid),
+            Err(e) => attempts.push((format, e))
+        }
+    }
+    Err(ParseError::NoFormatMatched(attempts))
+}
+
+/// for the readers that create a `SudokuSolver`, lists every conflict instead of only the first one `new` runs into
+pub(crate) fn to_solver(grid: [[u8; 9]; 9]) -> Result<SudokuSolver, ParseError> {
+    let conflicts = SudokuSolver::validate(&grid);
+    if !conflicts.is_empty() {
+        return Err(ParseError::Conflicts(conflicts));
+    }
+    SudokuSolver::new(grid).map_err(ParseError::Contradiction)
+}
+
+fn parse_cell(c: char) -> Result<u8, ParseError> {
+    match c {
+        '.' => Ok(0),
+        '0'..='9' => Ok(c as u8 - b'0'),
+        _ => Err(ParseError::InvalidCharacter(c))
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == '|' || c == '+' || c == '-' || c.is_whitespace()
+}
+
+fn from_cells(cells: &[u8]) -> Result<[[u8; 9]; 9], ParseError> {
+    if cells.len() != 81 {
+        return Err(ParseError::WrongCellCount(cells.len()));
+    }
+    let mut grid = [[0; 9]; 9];
+    for (i, value) in cells.iter().enumerate() {
+        let point = Point::from_index(i, 9);
+        grid[point.y][point.x] = *value;
+    }
+    Ok(grid)
+}
+
+fn from_rows(rows: &[Vec<u8>]) -> Result<[[u8; 9]; 9], ParseError> {
+    if rows.len() != 9 {
+        return Err(ParseError::WrongRowCount(rows.len()));
+    }
+    let mut grid = [[0; 9]; 9];
+    for (y, row) in rows.iter().enumerate() {
+        if row.len() != 9 {
+            return Err(ParseError::WrongRowLength { row: y, length: row.len() });
+        }
+        grid[y].copy_from_slice(row);
+    }
+    Ok(grid)
+}
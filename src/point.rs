@@ -1,6 +1,6 @@
-use std::ops::*;
+use core::ops::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Point<T> {
      pub x: T,
      pub y: T
@@ -15,6 +15,73 @@ impl<T> Point<T> {
     }
 }
 
+impl Point<usize> {
+    /// Maps a row-major index in `0..size * size` to the coordinates of that cell on a board `size` cells wide
+    ///
+    /// ```
+    /// use sudoku_solver_cli::point::Point;
+    ///
+    /// assert_eq!(Point::from_index(0, 9), Point::new(0, 0));
+    /// assert_eq!(Point::from_index(13, 9), Point::new(4, 1));
+    /// assert_eq!(Point::from_index(80, 9), Point::new(8, 8));
+    /// assert_eq!(Point::from_index(13, 4), Point::new(1, 3));
+    /// for size in [4, 9, 16] {
+    ///     assert!((0..size * size).all(|index| Point::from_index(index, size).to_index(size) == index));
+    /// }
+    /// ```
+    pub fn from_index(index: usize, size: usize) -> Self {
+        Point {
+            x: index % size,
+            y: index / size
+        }
+    }
+
+    /// Inverse of `from_index`
+    pub fn to_index(&self, size: usize) -> usize {
+        self.y * size + self.x
+    }
+
+    /// Returns `None` instead of underflowing when either coordinate would become negative
+    pub fn checked_sub(self, rhs: Point<usize>) -> Option<Self> {
+        Some(Point {
+            x: self.x.checked_sub(rhs.x)?,
+            y: self.y.checked_sub(rhs.y)?
+        })
+    }
+
+    /// Displays the point as a 1-based row/column reference, e.g. `r1c5` for `(x=4, y=0)`
+    pub fn rc(&self) -> RowColumn {
+        RowColumn(*self)
+    }
+}
+
+impl<T> Add<Point<T>> for Point<T>
+    where T: Add<T, Output = T>
+{
+    type Output = Point<T>;
+
+    fn add(self, rhs: Point<T>) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y
+        }
+    }
+}
+
+/// For `Point<usize>` this panics on underflow in debug builds, use `checked_sub` when that can happen
+impl<T> Sub<Point<T>> for Point<T>
+    where T: Sub<T, Output = T>
+{
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Point<T>) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y
+        }
+    }
+}
+
 impl<T> Mul<T> for Point<T>
     where T: Mul<T, Output = T> + Copy
 {
@@ -37,10 +104,26 @@ impl<T> From<(T, T)> for Point<T> {
     }
 }
 
-impl<T> std::fmt::Display for Point<T>
-    where T: std::fmt::Display
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> core::fmt::Display for Point<T>
+    where T: core::fmt::Display
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "(x={}, y={})", self.x, self.y)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// `rXcY` form of a point, see `Point::rc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowColumn(Point<usize>);
+
+impl core::fmt::Display for RowColumn {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "r{}c{}", self.0.y + 1, self.0.x + 1)
     }
 }
\ No newline at end of file
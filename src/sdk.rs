@@ -0,0 +1,45 @@
+//! The `.sdk` format of Sudoku Explainer, one puzzle per line with an optional difficulty after a `#`,
+//! e.g. `530070000600195000098000060800060003400803001700020006060000280000419005000080079#Easy`
+
+use crate::parse::{self, ParseError};
+use crate::sudoku::SudokuSolver;
+
+use std::io::{self, BufRead, Write};
+
+pub struct SdkParser;
+
+impl SdkParser {
+    /// Every puzzle of the file with its difficulty, blank lines and lines starting with `#` are skipped
+    pub fn read(reader: impl BufRead) -> impl Iterator<Item = Result<(SudokuSolver, Option<String>), ParseError>> {
+        reader.lines().filter_map(|line| {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(ParseError::Io(e.to_string())))
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (puzzle, difficulty) = match line.split_once('#') {
+                Some((puzzle, difficulty)) => (puzzle, Some(difficulty.trim()).filter(|difficulty| !difficulty.is_empty())),
+                None => (line, None)
+            };
+            Some(parse::from_line(puzzle)
+                .and_then(parse::to_solver)
+                .map(|solver| (solver, difficulty.map(str::to_string))))
+        })
+    }
+}
+
+pub struct SdkWriter;
+
+impl SdkWriter {
+    /// Writes the current board as one line, `0` for empty cells
+    pub fn write(mut writer: impl Write, solver: &SudokuSolver, difficulty: Option<&str>) -> io::Result<()> {
+        let line: String = solver.as_array().iter().flatten().map(|value| char::from(b'0' + value)).collect();
+        match difficulty {
+            Some(difficulty) => writeln!(writer, "{}#{}", line, difficulty),
+            None => writeln!(writer, "{}", line)
+        }
+    }
+}
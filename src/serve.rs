@@ -0,0 +1,136 @@
+//! `--serve`, an HTTP server on axum with `POST /solve` and `GET /health`, and with the `metrics` feature
+//! `GET /metrics` on `--metrics-port`.
+//! Solves run on the blocking thread pool of tokio so a slow solve never holds up other requests,
+//! at most `MAX_REQUESTS` requests are handled at once and every solve gives up after `SOLVE_TIMEOUT`
+
+#[cfg(feature = "metrics")]
+use crate::metrics::{SolveResult, METRICS};
+use crate::to_flat;
+use sudoku_solver_cli::parse;
+use sudoku_solver_cli::sudoku::{SolveConfig, SolveReport, SudokuError, SudokuSolver};
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::DefaultBodyLimit;
+#[cfg(feature = "metrics")]
+use axum::http::header;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tower::limit::ConcurrencyLimitLayer;
+
+use std::time::{Duration, Instant};
+
+/// requests with a bigger body are rejected, a puzzle in any format fits easily
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+/// more requests than this at once wait until one of them is done, so a flood of hard puzzles cannot take up
+/// every thread of the blocking pool
+const MAX_REQUESTS: usize = 64;
+
+/// served solves give up with `SudokuError::Timeout` after this long
+const SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Response = (StatusCode, Json<Value>);
+
+#[derive(Deserialize)]
+struct SolveRequest {
+    /// in any format `parse::from_flexible` accepts
+    puzzle: String
+}
+
+/// `metrics_port` has to be None without the `metrics` feature
+pub fn serve(port: u16, metrics_port: Option<u16>) -> std::io::Result<()> {
+    #[cfg(not(feature = "metrics"))]
+    assert!(metrics_port.is_none(), "the metrics are only served with the metrics feature");
+    tokio::runtime::Builder::new_multi_thread().enable_all().build()?.block_on(async move {
+        let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics_port) = metrics_port {
+            let metrics_listener = TcpListener::bind(("0.0.0.0", metrics_port)).await?;
+            println!("Serving metrics on port {}", metrics_port);
+            let metrics = Router::new().route("/metrics", get(metrics)).fallback(not_found);
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(metrics_listener, metrics).await {
+                    eprintln!("Error: {}", e);
+                }
+            });
+        }
+
+        let api = Router::new()
+            .route("/health", get(|| async { Json(json!({ "status": "ok" })) }))
+            .route("/solve", post(solve))
+            .fallback(not_found)
+            .layer(DefaultBodyLimit::max(MAX_BODY_LENGTH))
+            .layer(ConcurrencyLimitLayer::new(MAX_REQUESTS));
+        println!("Listening on port {}", port);
+        axum::serve(listener, api).await
+    })
+}
+
+async fn not_found() -> Response {
+    (StatusCode::NOT_FOUND, error_body("not found"))
+}
+
+#[cfg(feature = "metrics")]
+async fn metrics() -> ([(header::HeaderName, &'static str); 1], String) {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], METRICS.to_prometheus())
+}
+
+/// the response to `{"puzzle": "..."}`
+async fn solve(request: Result<Json<SolveRequest>, JsonRejection>) -> Response {
+    let request = match request {
+        Ok(Json(request)) => request,
+        Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+            return (StatusCode::PAYLOAD_TOO_LARGE, error_body("the request body is too large"));
+        },
+        Err(rejection) => return (rejection.status(), error_body("expected a JSON object with a \"puzzle\" string"))
+    };
+    let starting_state = match parse::from_flexible(&request.puzzle) {
+        Ok(starting_state) => starting_state,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&e.to_string()))
+    };
+
+    let start = Instant::now();
+    let solved = tokio::task::spawn_blocking(move || {
+        let config = SolveConfig { timeout: Some(SOLVE_TIMEOUT), ..SolveConfig::default() };
+        let mut sudoku = SudokuSolver::new(starting_state)?;
+        let report = sudoku.solve_with_report(&config)?;
+        Ok::<_, SudokuError>((sudoku, report))
+    }).await;
+    let Ok(solved) = solved else {
+        return (StatusCode::INTERNAL_SERVER_ERROR, error_body("solving panicked"));
+    };
+    record(&solved, start.elapsed());
+    match solved {
+        Ok((sudoku, report)) => {
+            (StatusCode::OK, Json(json!({
+                "solution": to_flat(sudoku.as_array()),
+                "solved": true,
+                "backtracks": report.backtracks
+            })))
+        },
+        Err(e) => {
+            (StatusCode::OK, Json(json!({ "solved": false, "error": e.to_string() })))
+        }
+    }
+}
+
+/// counts a served solve for `GET /metrics`
+#[cfg(feature = "metrics")]
+fn record(solved: &Result<(SudokuSolver, SolveReport), SudokuError>, duration: Duration) {
+    match solved {
+        Ok((_, report)) => METRICS.record(SolveResult::Success, duration, Some(report.backtracks)),
+        Err(SudokuError::Timeout) => METRICS.record(SolveResult::Timeout, duration, None),
+        Err(_) => METRICS.record(SolveResult::Failure, duration, None)
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+fn record(_: &Result<(SudokuSolver, SolveReport), SudokuError>, _: Duration) {}
+
+fn error_body(message: &str) -> Json<Value> {
+    Json(json!({ "error": message }))
+}
@@ -0,0 +1,48 @@
+//! The plain text format of SadMan Sudoku, nine lines of nine characters with `.` for empty cells
+//! and the digit otherwise, optionally after a header of lines starting with `#`:
+//!
+//! ```text
+//! #Source: generated
+//! #Difficulty: hard
+//! 53..7....
+//! 6..195...
+//! .98....6.
+//! 8...6...3
+//! 4..8.3..1
+//! 7...2...6
+//! .6....28.
+//! ...419..5
+//! ....8..79
+//! ```
+
+use crate::parse::{self, ParseError};
+use crate::sudoku::SudokuSolver;
+
+use alloc::string::String;
+
+impl SudokuSolver {
+    /// The current board with a `#` header line for each of `metadata`, every line ends with a line break
+    pub fn to_ss(&self, metadata: &[&str]) -> String {
+        let mut ss = String::new();
+        for line in metadata {
+            ss.push('#');
+            ss.push_str(line);
+            ss.push('\n');
+        }
+        for row in self.as_array() {
+            ss.extend(row.iter().map(|value| if *value == 0 { '.' } else { char::from(b'0' + value) }));
+            ss.push('\n');
+        }
+        ss
+    }
+
+    /// Reads what `to_ss` writes, the header is skipped. The grid may also use the `|` and `-` separators
+    /// of `parse::from_simple_sudoku_format`
+    pub fn from_ss(input: &str) -> Result<Self, ParseError> {
+        let grid: String = input.lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| [line, "\n"])
+            .collect();
+        parse::from_simple_sudoku_format(&grid)
+    }
+}
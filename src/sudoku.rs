@@ -1,51 +1,559 @@
+mod arrow;
+mod async_solver;
+mod backend;
+#[cfg(feature = "std")]
+mod batch;
+mod bitmask;
+mod board;
+mod coloring;
+mod dlx;
+mod generate;
+mod logical;
+mod renban;
+mod repair;
+mod report;
+mod samurai;
+mod sandwich;
+mod solutions;
+mod steps;
+mod techniques;
+mod thermometer;
+mod unique_rectangle;
+mod validate;
+mod whispers;
+
+pub use arrow::{Arrow, ArrowSudokuSolver};
+pub use async_solver::SolveCancel;
+pub use backend::{Solver, WfcSolver};
+#[cfg(feature = "std")]
+pub use batch::SolveOutcome;
+pub use bitmask::BitmaskSolver;
+pub use board::{Board, ConflictDisplay};
+pub use dlx::DlxSolver;
+pub use generate::Difficulty;
+pub use logical::LogicalOutcome;
+pub use renban::RenbanSudokuSolver;
+pub use report::{SolveConfig, SolveReport, StrategyLog, TraceEvent, ValueOrder};
+pub use samurai::SamuraiSudokuSolver;
+pub use sandwich::SandwichSudokuSolver;
+pub use solutions::{SolveMode, Solutions};
+pub use steps::SolveSteps;
+pub use techniques::{Deduction, ForcedPlacement, Technique, TechniquePipeline, TechniqueSet, TechniquesDisagree, Unit};
+pub use thermometer::ThermometerSudokuSolver;
+pub use validate::Conflict;
+pub use whispers::WhispersSudokuSolver;
+
 use crate::cell::Cell;
 use crate::point::Point;
+use generate::SplitMix64;
 
-use std::collections::HashSet;
+use alloc::{collections::BTreeSet, format, string::{String, ToString}, sync::Arc, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 type Sudoku = Vec<Vec<Cell>>;
 
+/// the peers of every cell of a regular board in row-major order, indexed by `y * 9 + x` and built at compile time.
+/// `peers` reads them and boards with regular regions start their own peers from them
+static STANDARD_PEERS: [[Point<usize>; 20]; 81] = standard_peers();
+
+const fn standard_peers() -> [[Point<usize>; 20]; 81] {
+    let mut peers = [[Point { x: 0, y: 0 }; 20]; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let (x, y) = (cell % 9, cell / 9);
+        let mut found = 0;
+        let mut other = 0;
+        while other < 81 {
+            let (other_x, other_y) = (other % 9, other / 9);
+            if other != cell && (other_x == x || other_y == y || (other_x / 3 == x / 3 && other_y / 3 == y / 3)) {
+                peers[cell][found] = Point { x: other_x, y: other_y };
+                found += 1;
+            }
+            other += 1;
+        }
+        cell += 1;
+    }
+    peers
+}
+
 #[derive(Debug, Clone)]
 pub struct SudokuSolver {
     board: Sudoku,
     previous_states: Vec<Sudoku>,
-    debug_view: String
+    debug_view: String,
+    /// additional regions of variants such as windoku, each has to contain all digits just like a normal region
+    extra_regions: Vec<BTreeSet<Point<usize>>>,
+    /// regions are `box_width` cells wide and `box_height` cells high, the board is `box_width * box_height` cells on each side
+    box_width: usize,
+    box_height: usize,
+    /// peers of every cell indexed by `y * size + x`, they never change so all clones share them
+    peers: Arc<Vec<Vec<Point<usize>>>>,
+    /// cells that were filled in the starting state, solving and backtracking never change them
+    givens: BTreeSet<Point<usize>>,
+    /// the order guesses try digits in, digits that are not in it are tried afterwards from the smallest up
+    collapse_order: Vec<u8>,
+    /// solving fails with `SudokuError::DepthLimitExceeded` instead of saving more states than this
+    max_backtrack_depth: Option<usize>
 }
 
-#[derive(Debug, Clone)]
-pub struct ErrorSudokuContainsAContradiction;
-impl std::fmt::Display for ErrorSudokuContainsAContradiction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The sudoku cannot be solved because it contains a contradiction in the initial state")
+/// What propagation ran into when it found a contradiction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contradiction {
+    /// `first` was collapsed to `digit` but `second`, which sees it, already holds `digit`
+    Duplicate { first: Point<usize>, second: Point<usize>, digit: u8 },
+    /// collapsing `source` to `digit` removed the last candidate of `cell`
+    NoCandidatesLeft { source: Point<usize>, cell: Point<usize>, digit: u8 }
+}
+impl core::fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Contradiction::Duplicate { first, second, digit } => write!(f, "{} and {} both contain {}", first.rc(), second.rc(), digit),
+            Contradiction::NoCandidatesLeft { source, cell, digit } => {
+                write!(f, "{} in {} leaves no candidates for {}", digit, source.rc(), cell.rc())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorSudokuContainsAContradiction {
+    /// None when the contradiction was not found by propagating a given, e.g. the grids of a samurai sudoku disagreeing
+    pub contradiction: Option<Contradiction>
+}
+impl core::fmt::Display for ErrorSudokuContainsAContradiction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The sudoku cannot be solved because it contains a contradiction in the initial state")?;
+        match self.contradiction {
+            Some(contradiction) => write!(f, ": {}", contradiction),
+            None => Ok(())
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct SudokuIsUnsolvable;
-impl std::fmt::Display for SudokuIsUnsolvable {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SudokuIsUnsolvable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "The sudoku contains a contradiction that could not be detected when initializing the SudokuSolver")
     }
 }
 
+/// Every way solving can fail, for the solve variants that can fail for more than one reason
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SudokuError {
+    /// see `ErrorSudokuContainsAContradiction`
+    InitialContradiction(Option<Contradiction>),
+    /// see `SudokuIsUnsolvable`
+    Unsolvable,
+    /// `SolveConfig::timeout` ran out
+    Timeout,
+    /// `SolveConfig::max_backtracks` was reached
+    BacktrackLimitReached,
+    /// the board is not `box_width * box_height` cells on each side or contains a value that does not fit on it
+    InvalidDimensions,
+    /// a point outside of the board
+    OutOfBounds,
+    /// a grid that should have been a solved sudoku has empty cells or breaks the rules
+    InvalidSolution,
+    /// `SolveConfig::cancel` was cancelled
+    Cancelled,
+    /// solving needed more saved states than `SudokuSolver::with_max_backtrack_depth` allows
+    DepthLimitExceeded,
+    /// `SolveMode::RequireUnique` found a second solution
+    NotUnique
+}
+impl core::fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SudokuError::InitialContradiction(contradiction) => ErrorSudokuContainsAContradiction { contradiction: *contradiction }.fmt(f),
+            SudokuError::Unsolvable => SudokuIsUnsolvable.fmt(f),
+            SudokuError::Timeout => write!(f, "The sudoku could not be solved before the timeout"),
+            SudokuError::BacktrackLimitReached => write!(f, "The sudoku could not be solved without exceeding the backtrack limit"),
+            SudokuError::InvalidDimensions => write!(f, "The size of the board does not match the size of its regions"),
+            SudokuError::OutOfBounds => write!(f, "The point is outside of the board"),
+            SudokuError::InvalidSolution => write!(f, "The grid is not a correctly solved sudoku"),
+            SudokuError::Cancelled => write!(f, "Solving was cancelled"),
+            SudokuError::DepthLimitExceeded => write!(f, "The sudoku could not be solved without exceeding the backtrack depth limit"),
+            SudokuError::NotUnique => write!(f, "The sudoku has more than one solution")
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for SudokuError {}
+
+impl From<ErrorSudokuContainsAContradiction> for SudokuError {
+    fn from(error: ErrorSudokuContainsAContradiction) -> Self {
+        SudokuError::InitialContradiction(error.contradiction)
+    }
+}
+
+impl From<SudokuIsUnsolvable> for SudokuError {
+    fn from(_: SudokuIsUnsolvable) -> Self {
+        SudokuError::Unsolvable
+    }
+}
+
+/// see `SudokuSolver::new`
+impl TryFrom<[[u8; 9]; 9]> for SudokuSolver {
+    type Error = ErrorSudokuContainsAContradiction;
+
+    fn try_from(starting_state: [[u8; 9]; 9]) -> Result<Self, Self::Error> {
+        SudokuSolver::new(starting_state)
+    }
+}
+
+/// 81 values in row-major order, see `SudokuSolver::from_flat`
+impl TryFrom<&[u8]> for SudokuSolver {
+    type Error = SudokuError;
+
+    fn try_from(cells: &[u8]) -> Result<Self, Self::Error> {
+        SudokuSolver::from_flat(cells)
+    }
+}
+
+/// see `SudokuSolver::as_array`
+impl From<&SudokuSolver> for [[u8; 9]; 9] {
+    fn from(solver: &SudokuSolver) -> Self {
+        solver.as_array()
+    }
+}
+
+/// see `SudokuSolver::as_array`
+impl From<SudokuSolver> for [[u8; 9]; 9] {
+    fn from(solver: SudokuSolver) -> Self {
+        solver.as_array()
+    }
+}
+
 // sudokuBuilder would be nice
 impl SudokuSolver {
     pub fn new(starting_state: [[u8; 9]; 9]) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Self::with_extra_regions(starting_state, Vec::new())
+    }
+
+    /// Like `new` but from any 9 rows of 9 values, e.g. a `Vec<Vec<u8>>`.
+    /// Fails with `SudokuError::InvalidDimensions` for any other shape or a value above 9, checked as the rows are consumed
+    #[doc(alias = "from_iter_2d")]
+    pub fn from_rows<I, R>(rows: I) -> Result<Self, SudokuError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = u8>
+    {
+        let mut starting_state = [[0; 9]; 9];
+        let mut row_count = 0;
+        for row in rows {
+            let target = starting_state.get_mut(row_count).ok_or(SudokuError::InvalidDimensions)?;
+            let mut length = 0;
+            for value in row {
+                if value > 9 {
+                    return Err(SudokuError::InvalidDimensions);
+                }
+                *target.get_mut(length).ok_or(SudokuError::InvalidDimensions)? = value;
+                length += 1;
+            }
+            if length != 9 {
+                return Err(SudokuError::InvalidDimensions);
+            }
+            row_count += 1;
+        }
+        if row_count != 9 {
+            return Err(SudokuError::InvalidDimensions);
+        }
+        Ok(Self::new(starting_state)?)
+    }
+
+    /// Like `new` but from the 81 values in row-major order
+    pub fn from_flat(cells: &[u8]) -> Result<Self, SudokuError> {
+        if cells.len() != 81 {
+            return Err(SudokuError::InvalidDimensions);
+        }
+        Self::from_rows(cells.chunks(9).map(|row| row.iter().copied()))
+    }
+
+    /// Windoku has four extra 3x3 regions with top left corners at (1, 1), (5, 1), (1, 5) and (5, 5)
+    pub fn new_windoku(starting_state: [[u8; 9]; 9]) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        let extra_regions = [(1, 1), (5, 1), (1, 5), (5, 5)].into_iter()
+            .map(|(x, y)| Self::square_region(Point::new(x, y)))
+            .collect();
+        Self::with_extra_regions(starting_state, extra_regions)
+    }
+
+    /// A board with rectangular regions, e.g. a 6x6 board with regions 3 cells wide and 2 cells high.
+    /// The board has to be `box_width * box_height` cells on each side and values go from 0 (empty) up to that side length,
+    /// see `to_rows` for getting the board back since `as_array` only fits 9x9 boards
+    pub fn with_box_dimensions(starting_state: Vec<Vec<u8>>, box_width: usize, box_height: usize) -> Result<Self, SudokuError> {
+        let size = box_width * box_height;
+        let fits = (1..=MAX_SIZE).contains(&size)
+            && starting_state.len() == size
+            && starting_state.iter().all(|row| row.len() == size && row.iter().all(|value| *value as usize <= size));
+        if !fits {
+            return Err(SudokuError::InvalidDimensions);
+        }
+        Ok(Self::with_layout(starting_state, box_width, box_height, Vec::new())?)
+    }
+
+    /// Sukaku, a puzzle given as the candidates of every cell instead of clues.
+    /// Fails with `SudokuError::InvalidDimensions` if a candidate is not a digit from 1 to 9
+    /// and with `SudokuError::InitialContradiction` if a cell has no candidates at all
+    pub fn from_candidates(candidates: [[Vec<u8>; 9]; 9]) -> Result<Self, SudokuError> {
+        if !candidates.iter().flatten().flatten().all(|value| (1..=9).contains(value)) {
+            return Err(SudokuError::InvalidDimensions);
+        }
+        if candidates.iter().flatten().any(|cell| cell.is_empty()) {
+            return Err(SudokuError::InitialContradiction(None));
+        }
+        let mut sudoku = SudokuSolver::new([[0; 9]; 9])?;
+        for (y, row) in candidates.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                sudoku.board[y][x] = Cell::new_with_candidates(cell);
+            }
+        }
+        Ok(sudoku)
+    }
+
+    /// A puzzle made from the cells of a finished `solution` where `keep` returns true, e.g. to build a puzzle
+    /// from a symmetric clue pattern. Fails with `SudokuError::InvalidSolution` if `solution` is not a correctly solved sudoku,
+    /// the puzzle itself can then only be contradictory if something is badly wrong but that is still reported as
+    /// `SudokuError::InitialContradiction`
+    pub fn from_solution(solution: [[u8; 9]; 9], keep: &dyn Fn(Point<usize>) -> bool) -> Result<Self, SudokuError> {
+        let is_solved = solution.iter().flatten().all(|value| (1..=9).contains(value))
+            && SudokuSolver::new(solution).is_ok_and(|solver| solver.check_if_correct());
+        if !is_solved {
+            return Err(SudokuError::InvalidSolution);
+        }
+        let mut puzzle = solution;
+        for point in (0..81).map(|i| Point::from_index(i, 9)).filter(|point| !keep(*point)) {
+            puzzle[point.y][point.x] = 0;
+        }
+        Ok(SudokuSolver::new(puzzle)?)
+    }
+
+    fn with_extra_regions(starting_state: [[u8; 9]; 9], extra_regions: Vec<BTreeSet<Point<usize>>>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Self::with_layout(starting_state.iter().map(|row| row.to_vec()).collect(), 3, 3, extra_regions)
+    }
+
+    fn with_layout(starting_state: Vec<Vec<u8>>, box_width: usize, box_height: usize, extra_regions: Vec<BTreeSet<Point<usize>>>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        let size = box_width * box_height;
         let mut sudoku = SudokuSolver {
-            board: vec![vec![Cell::new_empty(); 9]; 9],
-            previous_states: Vec::with_capacity(81), // sudoku is 9x9 so there is 81 max moves on a totally empty board
-            debug_view: String::new()
+            board: vec![vec![Cell::new_empty(size as u8); size]; size],
+            previous_states: Vec::with_capacity(size * size), // there is at most one move per cell on a totally empty board
+            debug_view: String::new(),
+            extra_regions,
+            box_width,
+            box_height,
+            peers: Arc::new(Vec::new()),
+            givens: BTreeSet::new(),
+            collapse_order: Vec::new(),
+            max_backtrack_depth: None
         };
+        sudoku.peers = Arc::new((0..size * size).map(|i| sudoku.get_relatives(Point::from_index(i, size))).collect());
+
+        sudoku.fill(&starting_state).map_err(|contradiction| ErrorSudokuContainsAContradiction { contradiction: Some(contradiction) })?;
+        sudoku.givens = (0..size * size).map(|i| Point::from_index(i, size))
+            .filter(|point| starting_state[point.y][point.x] != 0)
+            .collect();
+        Ok(sudoku)
+    }
 
-        for (y, row) in starting_state.iter().enumerate() {
+    /// collapses every non zero value of `values` into an empty board and propagates it
+    fn fill(&mut self, values: &[Vec<u8>]) -> Result<(), Contradiction> {
+        for (y, row) in values.iter().enumerate() {
             for (x, cell) in row.iter().enumerate() {
                 if *cell == 0  { continue; }
-                sudoku.board[y][x] = Cell::new_filled(*cell);
-                sudoku.propagate_collapse(Point::new(x, y), *cell).map_err(|_| ErrorSudokuContainsAContradiction)?;
+                self.board[y][x] = Cell::new_filled(*cell);
+                self.propagate_collapse(Point::new(x, y), *cell)?;
             }
         }
+        Ok(())
+    }
 
-        Ok(sudoku)
+    /// A new solver with only the givens of this one filled in, the same as constructing it again from the starting state.
+    /// Unlike `clone` it has none of the saved states or the progress of a solve, so solving it starts over from scratch
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let digits: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+    ///     .bytes().map(|b| b - b'0').collect();
+    /// let mut solved = SudokuSolver::from_flat(&digits).unwrap();
+    /// solved.solve().unwrap();
+    ///
+    /// let fresh = solved.fresh_copy();
+    /// let built = SudokuSolver::from_flat(&digits).unwrap();
+    /// assert_eq!(fresh.as_array(), built.as_array());
+    /// assert_eq!(fresh.entropy_map(), built.entropy_map());
+    /// assert_eq!(fresh.givens().collect::<Vec<_>>(), built.givens().collect::<Vec<_>>());
+    /// assert!(!fresh.is_solved());
+    /// ```
+    pub fn fresh_copy(&self) -> SudokuSolver {
+        let size = self.size();
+        let mut givens = vec![vec![0; size]; size];
+        for (point, value) in self.givens() {
+            givens[point.y][point.x] = value;
+        }
+        self.with_values(&givens).expect("the givens were already propagated without a contradiction")
+    }
+
+    /// `fresh_copy` under the name to reach for instead of `clone` when the same puzzle is solved many times,
+    /// the saved states of a solve, which can be far bigger than the board itself, are never copied
+    pub fn clone_initial(&self) -> SudokuSolver {
+        self.fresh_copy()
+    }
+
+    /// Drops the saved states and the debug view but keeps the board as it is,
+    /// afterwards the solve cannot backtrack past the current board anymore
+    pub fn clear_solve_state(&mut self) {
+        self.previous_states = Vec::new();
+        self.debug_view = String::new();
+    }
+
+    /// A solver with the same layout and settings but a board built from `values`, which also become its givens
+    fn with_values(&self, values: &[Vec<u8>]) -> Result<SudokuSolver, Contradiction> {
+        let size = self.size();
+        let mut solver = self.search_copy();
+        solver.board = vec![vec![Cell::new_empty(size as u8); size]; size];
+        solver.fill(values)?;
+        solver.givens = (0..size * size).map(|i| Point::from_index(i, size))
+            .filter(|point| values[point.y][point.x] != 0)
+            .collect();
+        Ok(solver)
+    }
+
+    /// Clears the cell at `p`, which stops being a given, and recomputes every candidate from the collapsed cells that are left.
+    /// Propagation removed the cleared value from the peers without remembering which removals it caused,
+    /// so the whole board is rebuilt, which costs about as much as creating a new solver.
+    /// The saved states are dropped and so are any candidates that a variant's strategy removed
+    ///
+    /// ```
+    /// use sudoku_solver_cli::point::Point;
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let digits: Vec<u8> = "200000800005140003000000000030860007900400600000005100090370000076020000001009500"
+    ///     .bytes().map(|b| b - b'0').collect();
+    /// let built = |without: &[Point<usize>]| {
+    ///     let mut cells = digits.clone();
+    ///     for point in without {
+    ///         cells[point.to_index(9)] = 0;
+    ///     }
+    ///     SudokuSolver::from_flat(&cells).unwrap()
+    /// };
+    /// // the values, every candidate and the givens
+    /// let state = |sudoku: &SudokuSolver| {
+    ///     let candidates: Vec<bool> = (0..81).flat_map(|i| (1..=9).map(move |digit| (Point::from_index(i, 9), digit)))
+    ///         .map(|(point, digit)| sudoku.is_valid_placement(point, digit))
+    ///         .collect();
+    ///     (sudoku.as_array(), candidates, sudoku.givens().collect::<Vec<_>>())
+    /// };
+    ///
+    /// let clues = [Point::new(0, 0), Point::new(6, 0), Point::new(3, 1)];
+    /// for order in [[0, 1, 2], [2, 1, 0], [1, 2, 0]] {
+    ///     let mut sudoku = built(&[]);
+    ///     for removed in 1..=3 {
+    ///         sudoku.remove(clues[order[removed - 1]]).unwrap();
+    ///         let without: Vec<Point<usize>> = order[..removed].iter().map(|i| clues[*i]).collect();
+    ///         assert_eq!(state(&sudoku), state(&built(&without)));
+    ///     }
+    /// }
+    ///
+    /// // putting r1c1 back after removing it and then removing r1c7 is the same as only removing r1c7
+    /// let mut sudoku = built(&[]);
+    /// sudoku.remove(clues[0]).unwrap();
+    /// let mut cells = sudoku.to_rows();
+    /// cells[0][0] = 2;
+    /// let mut sudoku = SudokuSolver::from_rows(cells).unwrap();
+    /// sudoku.remove(clues[1]).unwrap();
+    /// assert_eq!(state(&sudoku), state(&built(&clues[1..2])));
+    /// ```
+    pub fn remove(&mut self, p: Point<usize>) -> Result<(), SudokuError> {
+        let size = self.size();
+        if p.x >= size || p.y >= size {
+            return Err(SudokuError::OutOfBounds);
+        }
+        let mut values = self.to_rows();
+        values[p.y][p.x] = 0;
+        self.board = vec![vec![Cell::new_empty(size as u8); size]; size];
+        self.previous_states.clear();
+        self.fill(&values).map_err(|contradiction| SudokuError::InitialContradiction(Some(contradiction)))?;
+        self.givens.remove(&p);
+        self.debug_view = self.to_string();
+        Ok(())
+    }
+
+    /// Limits how many states solving can save to backtrack to, each of them is a copy of the whole board,
+    /// so this bounds the memory a solve takes, and the search tree along with it. Solving fails with
+    /// `SudokuError::DepthLimitExceeded` when it needs more and leaves the board partially solved where it stopped
+    pub fn with_max_backtrack_depth(mut self, max_depth: usize) -> Self {
+        self.max_backtrack_depth = Some(max_depth);
+        self
+    }
+
+    /// Makes guesses try the digits in `order` instead of from the smallest up, which decides
+    /// which solution is found first on a board with several of them
+    pub fn set_collapse_order(&mut self, order: [u8; 9]) {
+        self.collapse_order = order.to_vec();
+    }
+
+    /// was the cell filled in the starting state, solving never changes which cells are givens
+    pub fn is_given(&self, p: Point<usize>) -> bool {
+        self.givens.contains(&p)
+    }
+
+    /// the cells filled in the starting state with their values, in row-major order
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, TraceEvent};
+    ///
+    /// let digits: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+    ///     .bytes().map(|b| b - b'0').collect();
+    /// let mut sudoku = SudokuSolver::from_flat(&digits).unwrap();
+    /// let givens: Vec<_> = sudoku.givens().collect();
+    /// assert_eq!(givens.len(), 21);
+    ///
+    /// // the solve backtracks many times but never collapses a given again or takes one away
+    /// let report = sudoku.solve_with_report(&SolveConfig { trace: true, ..SolveConfig::default() }).unwrap();
+    /// assert!(report.backtracks > 0);
+    /// assert!(report.trace.iter().all(|event| !matches!(event, TraceEvent::Collapse { cell, .. } if sudoku.is_given(*cell))));
+    /// assert_eq!(sudoku.givens().collect::<Vec<_>>(), givens);
+    /// assert!(givens.iter().all(|(point, value)| sudoku.as_array()[point.y][point.x] == *value));
+    /// ```
+    pub fn givens(&self) -> impl Iterator<Item = (Point<usize>, u8)> + '_ {
+        (0..self.size() * self.size()).map(|i| Point::from_index(i, self.size()))
+            .filter(|point| self.is_given(*point))
+            .filter_map(|point| self.get_cell(&point).value().map(|value| (point, value)))
+    }
+
+    fn givens_are_intact(&self) -> bool {
+        self.givens.iter().all(|point| self.get_cell(point).is_collapsed())
+    }
+
+    /// side length of the board, 9 for a regular sudoku
+    pub fn size(&self) -> usize {
+        self.box_width * self.box_height
+    }
+
+    /// The 20 cells sharing a row, column or region with `point` on a regular 9x9 board, in row-major order.
+    /// They come from the same table propagation reads on regular boards
+    ///
+    /// ```
+    /// use sudoku_solver_cli::point::Point;
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// for point in (0..81).map(|i| Point::from_index(i, 9)) {
+    ///     let peers: Vec<Point<usize>> = SudokuSolver::peers(point).collect();
+    ///     assert_eq!(peers.len(), 20);
+    ///     assert!(!peers.contains(&point));
+    ///     assert!(peers.iter().all(|peer| SudokuSolver::peers(*peer).any(|back| back == point)));
+    ///     assert!(peers.windows(2).all(|pair| pair[0].to_index(9) < pair[1].to_index(9)));
+    /// }
+    /// ```
+    pub fn peers(point: Point<usize>) -> impl Iterator<Item = Point<usize>> {
+        STANDARD_PEERS[point.to_index(9)].into_iter()
+    }
+
+    /// peers of the cell on this board, which also counts extra regions and other board sizes
+    fn get_peers(&self, cell_coords: Point<usize>) -> &[Point<usize>] {
+        &self.peers[cell_coords.to_index(self.size())]
     }
 
     fn get_cell(&self, cell_coords: &Point<usize>) -> &Cell {
@@ -56,73 +564,399 @@ impl SudokuSolver {
         &mut self.board[cell_coords.y][cell_coords.x]
     }
 
+    fn get_candidates(&self, cell_coords: &Point<usize>) -> BTreeSet<u8> {
+        self.get_cell(cell_coords).candidates().collect()
+    }
+
+    /// Could `value` go into the cell at `point` right now, without placing it.
+    /// False for a collapsed cell, when a peer already holds `value`, and when propagation
+    /// already removed `value` from the cell's candidates even if no peer visibly holds it
+    ///
+    /// ```
+    /// use sudoku_solver_cli::point::Point;
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut cells = [[0; 9]; 9];
+    /// cells[0][3..].copy_from_slice(&[2, 3, 4, 5, 6, 7]);
+    /// let mut sudoku = SudokuSolver::new(cells).unwrap();
+    /// // already collapsed, even to the same digit
+    /// assert!(!sudoku.is_valid_placement(Point::new(3, 0), 2));
+    /// // r2c4 sees the 2 in r1c4
+    /// assert!(!sudoku.is_valid_placement(Point::new(3, 1), 2));
+    /// assert!(sudoku.is_valid_placement(Point::new(3, 1), 1));
+    ///
+    /// // 1 of row 1 can only go into region 1, which clears it out of r2c1 without a 1 anywhere it can see
+    /// assert!(sudoku.is_valid_placement(Point::new(0, 1), 1));
+    /// sudoku.apply_box_line_reduction().unwrap();
+    /// assert!(!sudoku.is_valid_placement(Point::new(0, 1), 1));
+    /// ```
+    pub fn is_valid_placement(&self, point: Point<usize>, value: u8) -> bool {
+        if point.x >= self.size() || point.y >= self.size() || !(1..=self.size() as u8).contains(&value) {
+            return false;
+        }
+        let cell = self.get_cell(&point);
+        !cell.is_collapsed() && cell.contains(value)
+            && self.get_peers(point).iter().all(|relative| self.get_cell(relative).value() != Some(value))
+    }
+
+    /// The first uncollapsed cell in row-major order that has no candidates left, so the board can no longer be solved.
+    /// Propagation stops at such a cell, this finds one on a board without changing anything
+    pub fn first_contradiction(&self) -> Option<Point<usize>> {
+        (0..self.size() * self.size()).map(|i| Point::from_index(i, self.size()))
+            .find(|point| !self.get_cell(point).is_collapsed() && self.get_cell(point).get_entropy() == 0)
+    }
+
+    /// candidates as a bitmask where bit `d` is set if digit `d` is a candidate, only for the 9x9 variants
+    fn get_candidate_mask(&self, cell_coords: &Point<usize>) -> u16 {
+        (1..=9).filter(|value| self.get_cell(cell_coords).contains(*value)).fold(0, |mask, value| mask | (1 << value))
+    }
+
     pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable>{
-        let mut solved = false;
-
-        while !solved {
-            match self.solve_iteration() {
-                Ok(true) => solved = true,
-                Ok(false) => continue,
-                Err(_) => match self.previous_states.pop() {
-                    Some(previous_state) => self.board = previous_state,
-                    None => break
-                }
+        self.solve_with_strategy(&mut |_| Ok(()))
+    }
+
+    /// Solves just like `solve` but within the limits of `config`, and returns statistics about the solve
+    pub fn solve_with_report(&mut self, config: &SolveConfig) -> Result<SolveReport, SudokuError> {
+        self.search(config, &mut |_| Ok(()))
+    }
+
+    /// Continues a solve that stopped early, e.g. with `SudokuError::Timeout` or `SudokuError::BacktrackLimitReached`,
+    /// from the board, candidates and saved states it stopped at. Nothing is recomputed, the candidates left on the board
+    /// are trusted, and the result is the same as if the first solve had never stopped
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuError, SudokuSolver};
+    ///
+    /// let digits: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let mut uninterrupted = SudokuSolver::from_flat(&digits).unwrap();
+    /// uninterrupted.solve().unwrap();
+    ///
+    /// let mut paused = SudokuSolver::from_flat(&digits).unwrap();
+    /// let limited = SolveConfig { max_backtracks: Some(5), ..SolveConfig::default() };
+    /// assert!(matches!(paused.solve_with_report(&limited), Err(SudokuError::BacktrackLimitReached)));
+    /// paused.resume().unwrap();
+    /// assert_eq!(paused.as_array(), uninterrupted.as_array());
+    /// ```
+    pub fn resume(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        self.run_search(&SolveConfig::default(), &mut |_| Ok(())).map(|_| ()).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    /// Solves just like `solve` and also returns the board at every guess the search made, in the order it made them.
+    /// Forced placements are not recorded, so this shows where the search branched, including the branches it
+    /// backtracked out of. The snapshots are returned even when the board turns out to be unsolvable
+    pub fn solve_with_snapshots(&mut self) -> (Result<(), SudokuIsUnsolvable>, Vec<[[u8; 9]; 9]>) {
+        let config = SolveConfig { snapshots: true, ..SolveConfig::default() };
+        let mut report = SolveReport::default();
+        let result = self.run_search_into(&config, &mut |_| Ok(()), &mut report);
+        (result.map_err(|_| SudokuIsUnsolvable), report.snapshots)
+    }
+
+    /// Solves just like `solve` and counts the deductions of every technique and the guesses it took, see `StrategyLog`.
+    /// The more of the harder techniques and guesses a puzzle needs, the harder it is
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let digits: Vec<u8> = "200000800005140003000000000030860007900400600000005100090370000076020000001009500"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let log = SudokuSolver::from_flat(&digits).unwrap().solve_with_strategy_log().unwrap();
+    /// assert_eq!(log.guesses, 0);
+    /// assert!(log.hidden_singles > 0);
+    /// assert_eq!(log.naked_singles + log.hidden_singles, 81 - 24);
+    /// ```
+    pub fn solve_with_strategy_log(&mut self) -> Result<StrategyLog, SudokuIsUnsolvable> {
+        self.solve_with_report(&SolveConfig::default()).map(|report| StrategyLog::from(&report)).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    /// `strategy` runs before every iteration and on the finished board, it may remove candidates to enforce
+    /// the extra rules of a variant and returns Err when the board breaks them, which is handled like any other contradiction
+    fn solve_with_strategy(&mut self, strategy: &mut dyn FnMut(&mut SudokuSolver) -> Result<(), ()>) -> Result<(), SudokuIsUnsolvable> {
+        self.search(&SolveConfig::default(), strategy).map(|_| ()).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    fn search(&mut self, config: &SolveConfig, strategy: &mut dyn FnMut(&mut SudokuSolver) -> Result<(), ()>) -> Result<SolveReport, SudokuError> {
+        // anything a technique forces on the starting board has to be part of every solution,
+        // so a solution disagreeing with one of them means the technique is wrong.
+        // A board reached through a guess is not a starting board, backtracking can undo what is forced on it
+        #[cfg(debug_assertions)]
+        let forced_placements = if self.previous_states.is_empty() { self.forced_placements() } else { Vec::new() };
+
+        let report = self.run_search(config, strategy)?;
+
+        #[cfg(debug_assertions)]
+        for placement in forced_placements {
+            debug_assert!(self.get_cell(&placement.cell).contains(placement.value),
+                          "{} forced {} into {} but the solution disagrees", placement.technique, placement.value, placement.cell.rc());
+        }
+        Ok(report)
+    }
+
+    /// The solving loop, continues from whatever board and saved states the solver currently has
+    fn run_search(&mut self, config: &SolveConfig, strategy: &mut dyn FnMut(&mut SudokuSolver) -> Result<(), ()>) -> Result<SolveReport, SudokuError> {
+        let mut report = SolveReport::default();
+        self.run_search_into(config, strategy, &mut report)?;
+        Ok(report)
+    }
+
+    /// `run_search` writing into `report`, which keeps what was recorded when the search fails
+    fn run_search_into(&mut self, config: &SolveConfig, strategy: &mut dyn FnMut(&mut SudokuSolver) -> Result<(), ()>, report: &mut SolveReport) -> Result<(), SudokuError> {
+        #[cfg(feature = "std")]
+        let start = Instant::now();
+        report.value_order = config.value_order;
+
+        loop {
+            #[cfg(feature = "std")]
+            if config.timeout.is_some_and(|timeout| start.elapsed() > timeout) {
+                return Err(SudokuError::Timeout);
+            }
+            if config.cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled()) {
+                return Err(SudokuError::Cancelled);
+            }
+            if self.search_step(config, strategy, report)? {
+                break;
             }
-            self.debug_view = self.to_string();
         }
 
-        if solved {
-            Ok(())
-        } else {
-            Err(SudokuIsUnsolvable)
+        if config.trace {
+            report.trace.push(TraceEvent::Solved);
+        }
+        #[cfg(feature = "std")]
+        {
+            report.elapsed = start.elapsed();
+        }
+        Ok(())
+    }
+
+    /// One iteration of the solving loop, which either collapses a cell or backtracks.
+    /// Returns true once the board is solved and Err when the search has to stop
+    fn search_step(&mut self, config: &SolveConfig, strategy: &mut dyn FnMut(&mut SudokuSolver) -> Result<(), ()>, report: &mut SolveReport) -> Result<bool, SudokuError> {
+        report.iterations += 1;
+
+        // a strategy does not say what broke its rules, so only contradictions found by propagation are known
+        let iteration = strategy(self).map_err(|_| None)
+            .and_then(|_| self.solve_iteration(config, report).map_err(Some));
+        match iteration {
+            Ok(true) => {
+                report.leaves_reached += 1;
+                self.debug_view = self.to_string();
+                debug_assert!(self.givens_are_intact(), "solving changed a given");
+                return Ok(true);
+            },
+            Ok(false) if self.max_backtrack_depth.is_some_and(|limit| self.previous_states.len() > limit) => {
+                return Err(SudokuError::DepthLimitExceeded);
+            },
+            Ok(false) => {},
+            Err(contradiction) => {
+                report.leaves_reached += 1;
+                match self.previous_states.pop() {
+                    // the saved state is still restored so that `resume` can continue from it
+                    Some(previous_state) if config.max_backtracks.is_some_and(|limit| report.backtracks >= limit) => {
+                        self.board = previous_state;
+                        self.debug_view = self.to_string();
+                        return Err(SudokuError::BacktrackLimitReached);
+                    },
+                    Some(previous_state) => {
+                        self.board = previous_state;
+                        debug_assert!(self.givens_are_intact(), "backtracking changed a given");
+                        report.backtracks += 1;
+                        if config.trace {
+                            report.trace.push(TraceEvent::Backtrack { depth: self.previous_states.len(), contradiction });
+                        }
+                    },
+                    None => return Err(SudokuError::Unsolvable)
+                }
+            }
         }
+        self.debug_view = self.to_string();
+        Ok(false)
     }
 
     // returns true if sudoku is solved, false if not and Err if there is a contradiction
-    fn solve_iteration(&mut self) -> Result<bool, ()> {
-        match self.get_coords_of_uncollapsed_cell_with_lowest_entropy() {
-            Some(cell_coords) => { self.collapse_cell_and_save_state(cell_coords)?; Ok(false) },
-            None => Ok(true) // sudoku is solved
+    fn solve_iteration(&mut self, config: &SolveConfig, report: &mut SolveReport) -> Result<bool, Contradiction> {
+        self.deduce(config, report)?;
+        // made from the seed and the iteration, the generator needs no state kept between iterations
+        let mut rng = config.seed.map(|seed| SplitMix64::for_iteration(seed, report.iterations));
+        let cell_coords = match &mut rng {
+            Some(rng) => self.random_cell_with_lowest_entropy(rng),
+            None => self.get_coords_of_uncollapsed_cell_with_lowest_entropy()
+        };
+        let Some(cell_coords) = cell_coords else { return Ok(true) }; // sudoku is solved
+        let order = self.guess_order(cell_coords, config.value_order, rng.as_mut());
+        self.collapse_cell_and_save_state(cell_coords, order.as_deref(), config, report)?;
+        Ok(false)
+    }
+
+    /// The order a guess on `cell_coords` tries its digits in, None for the collapse order of the solver
+    fn guess_order(&self, cell_coords: Point<usize>, value_order: ValueOrder, rng: Option<&mut SplitMix64>) -> Option<Vec<u8>> {
+        if rng.is_none() && value_order == ValueOrder::CollapseOrder {
+            return None;
+        }
+        let mut order: Vec<u8> = self.collapse_order.iter().copied()
+            .chain((1..=self.size() as u8).filter(|digit| !self.collapse_order.contains(digit)))
+            .collect();
+        if let Some(rng) = rng {
+            rng.shuffle(&mut order);
+        }
+        if value_order == ValueOrder::LeastConstraining {
+            order.retain(|digit| self.get_cell(&cell_coords).contains(*digit));
+            order.sort_by_cached_key(|digit| self.get_peers(cell_coords).iter()
+                .filter(|peer| !self.get_cell(peer).is_collapsed() && self.get_cell(peer).contains(*digit))
+                .count());
         }
+        Some(order)
     }
 
-    fn collapse_cell_and_save_state(&mut self, cell_coords: Point<usize>) -> Result<(), ()> {
-        let cell = self.get_cell_mut(&cell_coords);
-        let should_save = cell.get_entropy() > 1;
-        let value_with_collapsed_num_removed = cell.collapse();
+    /// Applies the techniques of `SolveConfig::pipeline` until none of them finds anything, without guessing
+    fn deduce(&mut self, config: &SolveConfig, report: &mut SolveReport) -> Result<(), Contradiction> {
+        let pipeline = &config.pipeline;
+        let hidden_singles = pipeline.contains(Technique::HiddenSingle);
+        if hidden_singles {
+            self.collapse_hidden_singles(config, report)?;
+        }
+        // singles are cheaper to find, so they are exhausted again after every elimination
+        while self.eliminate_candidates(pipeline, config, report)? {
+            if hidden_singles {
+                self.collapse_hidden_singles(config, report)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `order` is the order to try the digits in instead of the collapse order of the solver
+    fn collapse_cell_and_save_state(&mut self, cell_coords: Point<usize>, order: Option<&[u8]>, config: &SolveConfig, report: &mut SolveReport) -> Result<(), Contradiction> {
+        // the board is borrowed directly so that the collapse order can be read at the same time
+        let cell = &mut self.board[cell_coords.y][cell_coords.x];
+        let entropy = cell.get_entropy() as usize;
+        let should_save = entropy > 1;
+        let value_with_collapsed_num_removed = cell.collapse(order.unwrap_or(&self.collapse_order));
         let Cell::Collapsed(collapsed_to_num) = *cell else { unreachable!() };
 
         if should_save {
             let mut board = self.board.clone();
             board[cell_coords.y][cell_coords.x] = value_with_collapsed_num_removed;
             self.previous_states.push(board);
+            report.guesses += 1;
+            report.max_depth = report.max_depth.max(self.previous_states.len());
+            if config.snapshots {
+                report.snapshots.push(self.as_array());
+            }
+        } else {
+            report.forced_placements += 1;
         }
+        self.record_collapse(cell_coords, collapsed_to_num, entropy, config, report);
+        self.propagate_and_collapse_forced(cell_coords, collapsed_to_num, config, report)
+    }
 
-        self.propagate_collapse(cell_coords, collapsed_to_num)?;
+    /// Propagates the collapse of `cell_coords` and immediately collapses every peer left with a single candidate,
+    /// propagating those in turn until nothing is forced anymore. Nothing is saved since none of these are guesses,
+    /// a contradiction anywhere in the cascade fails the whole collapse
+    fn propagate_and_collapse_forced(&mut self, cell_coords: Point<usize>, value: u8, config: &SolveConfig, report: &mut SolveReport) -> Result<(), Contradiction> {
+        let mut queue = vec![(cell_coords, value)];
+        while let Some((cell_coords, value)) = queue.pop() {
+            self.propagate_collapse(cell_coords, value)?;
+            let peers = Arc::clone(&self.peers);
+            for peer in &peers[cell_coords.to_index(self.size())] {
+                let Some(forced) = self.get_cell(peer).single_candidate() else { continue };
+                self.board[peer.y][peer.x].collapse(&self.collapse_order);
+                report.forced_placements += 1;
+                report.cascaded_placements += 1;
+                self.record_collapse(*peer, forced, 1, config, report);
+                queue.push((*peer, forced));
+            }
+        }
         Ok(())
     }
 
-    fn propagate_collapse(&mut self, cell_coords: Point<usize>, value: u8) -> Result<(), ()> {
-        let relatives_coords = self.get_relatives(cell_coords);
-        for relative_cords in relatives_coords {
-            self.get_cell_mut(&relative_cords).remove(value)?;
+    /// the statistics and trace of collapsing a cell that had `entropy` candidates, before its collapse is propagated
+    fn record_collapse(&self, cell_coords: Point<usize>, value: u8, entropy: usize, config: &SolveConfig, report: &mut SolveReport) {
+        if config.trace {
+            report.trace.push(TraceEvent::Collapse { cell: cell_coords, value, guess: entropy > 1 });
+        }
+        report.nodes_visited += 1;
+        if report.branching.len() <= entropy {
+            report.branching.resize(entropy + 1, 0);
+        }
+        report.branching[entropy] += 1;
+        report.branches_pruned += self.get_peers(cell_coords).iter()
+            .filter(|relative| !self.get_cell(relative).is_collapsed() && self.get_cell(relative).contains(value))
+            .count();
+    }
+
+    fn propagate_collapse(&mut self, cell_coords: Point<usize>, value: u8) -> Result<(), Contradiction> {
+        // the peers are shared, so cloning the Arc lets them be read while cells are mutated
+        let peers = Arc::clone(&self.peers);
+        for relative_cords in &peers[cell_coords.to_index(self.size())] {
+            let relative = self.get_cell_mut(relative_cords);
+            let contradiction = if relative.is_collapsed() {
+                Contradiction::Duplicate { first: cell_coords, second: *relative_cords, digit: value }
+            } else {
+                Contradiction::NoCandidatesLeft { source: cell_coords, cell: *relative_cords, digit: value }
+            };
+            relative.remove(value).map_err(|_| contradiction)?;
         }
         Ok(())
     }
 
     fn get_relatives(&self, cell_coords: Point<usize>) -> Vec<Point<usize>> {
-        // let mut relatives = HashSet::with_capacity(20); // row + column + small square - repetitions = 3*8-4 = 20
-        let mut relatives = HashSet::with_capacity(20);
-        relatives.extend(self.get_row(cell_coords.y));
-        relatives.extend(self.get_column(cell_coords.x));
-        relatives.extend(self.get_region(cell_coords));
+        let mut relatives = BTreeSet::new();
+        if self.box_width == 3 && self.box_height == 3 {
+            relatives.extend(STANDARD_PEERS[cell_coords.to_index(9)]);
+        } else {
+            relatives.extend(self.get_row(cell_coords.y));
+            relatives.extend(self.get_column(cell_coords.x));
+            relatives.extend(self.get_region(cell_coords));
+        }
+        for region in self.extra_regions.iter().filter(|region| region.contains(&cell_coords)) {
+            relatives.extend(region);
+        }
         relatives.remove(&cell_coords);
-        relatives.into_iter().collect()
+        let mut relatives: Vec<_> = relatives.into_iter().collect();
+        relatives.sort_by_key(|point| (point.y, point.x));
+        relatives
+    }
+
+    /// The index of the region the cell belongs to, counting from 0 at the top left to the right and then down,
+    /// so 0 to 8 on a regular board. It is the same order as the regions are checked in
+    pub fn region_index(&self, point: Point<usize>) -> usize {
+        let regions_per_row = self.size() / self.box_width;
+        point.y / self.box_height * regions_per_row + point.x / self.box_width
+    }
+
+    /// The candidates left in the uncollapsed cells of each region added up, indexed by `region_index`.
+    /// A heat map of the board: the lower the count the more constrained the region, and a solved region counts 0
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut sudoku = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    /// assert_eq!(sudoku.region_candidate_counts(), vec![81; 9]);
+    /// sudoku.solve().unwrap();
+    /// assert_eq!(sudoku.region_candidate_counts(), vec![0; 9]);
+    /// ```
+    pub fn region_candidate_counts(&self) -> Vec<usize> {
+        self.get_regions().iter()
+            .map(|region| region.iter()
+                .filter(|point| !self.get_cell(point).is_collapsed())
+                .map(|point| self.get_cell(point).get_entropy() as usize)
+                .sum())
+            .collect()
     }
 
     /// Return the coordinates of the top left corner of the region that the cell belongs to
     fn get_region_coords(&self, cell_coords: Point<usize>) -> Point<usize> {
-        Point::new(cell_coords.x / 3, cell_coords.y / 3) * 3
+        Point::new(cell_coords.x / self.box_width * self.box_width, cell_coords.y / self.box_height * self.box_height)
+    }
+
+    /// one of the uncollapsed cells with the fewest candidates picked by `rng`, None once every cell is collapsed
+    fn random_cell_with_lowest_entropy(&self, rng: &mut SplitMix64) -> Option<Point<usize>> {
+        let size = self.size();
+        let open: Vec<Point<usize>> = (0..size * size).map(|i| Point::from_index(i, size))
+            .filter(|point| !self.get_cell(point).is_collapsed())
+            .collect();
+        let lowest = open.iter().map(|point| self.get_cell(point).get_entropy()).min()?;
+        let lowest: Vec<Point<usize>> = open.into_iter().filter(|point| self.get_cell(point).get_entropy() == lowest).collect();
+        Some(lowest[(rng.next() % lowest.len() as u64) as usize])
     }
 
     fn get_coords_of_uncollapsed_cell_with_lowest_entropy(& self) -> Option<Point<usize>> {
@@ -145,13 +979,62 @@ impl SudokuSolver {
         cell
     }
 
+    /// The current board with `0` for every uncollapsed cell, only for 9x9 boards
+    pub fn as_array(&self) -> [[u8; 9]; 9] {
+        debug_assert_eq!(self.size(), 9, "as_array called on a board that is not 9x9, use to_rows");
+        let mut array = [[0; 9]; 9];
+        for (y, row) in self.board.iter().enumerate().take(9) {
+            for (x, cell) in row.iter().enumerate().take(9) {
+                array[y][x] = cell.value().unwrap_or(0);
+            }
+        }
+        array
+    }
+
+    /// The number of candidates left in every cell, 1 for a collapsed cell, only for 9x9 boards.
+    /// A heat map of where the board is most constrained, a cell without candidates would be a contradiction
+    /// so on a board the solver built every entry is from 1 to 9
+    pub fn entropy_map(&self) -> [[u8; 9]; 9] {
+        debug_assert_eq!(self.size(), 9, "entropy_map called on a board that is not 9x9");
+        let mut map = [[0; 9]; 9];
+        for (y, row) in self.board.iter().enumerate().take(9) {
+            for (x, cell) in row.iter().enumerate().take(9) {
+                map[y][x] = cell.candidates().count() as u8;
+            }
+        }
+        map
+    }
+
+    /// The current board with `0` for every uncollapsed cell, for boards of any size
+    pub fn to_rows(&self) -> Vec<Vec<u8>> {
+        self.board.iter().map(|row| row.iter().map(|cell| cell.value().unwrap_or(0)).collect()).collect()
+    }
+
+    /// Validates the whole board, true only if every unit holds every digit. See `is_solved` to only ask whether it is full
     pub fn check_if_correct(&self) -> bool {
-        self.check_rows() && self.check_columns() && self.check_regions()
+        self.check_rows() && self.check_columns() && self.check_regions() && self.check_extra_regions()
+    }
+
+    /// True once every cell is collapsed, without checking the board against the rules.
+    /// Propagation never lets a collapsed cell conflict with its peers, so a solved board is also correct
+    /// unless its cells were set some other way
+    pub fn is_solved(&self) -> bool {
+        self.board.iter().flatten().all(Cell::is_collapsed)
+    }
+
+    /// Like `check_if_correct` but only for the collapsed cells, true as long as no row, column or region
+    /// holds the same digit twice, however many cells are still empty
+    pub fn check_if_no_conflicts(&self) -> bool {
+        let size = self.size();
+        (0..size * size).map(|i| Point::from_index(i, size)).all(|point| match self.get_cell(&point).value() {
+            Some(value) => self.get_peers(point).iter().all(|peer| self.get_cell(peer).value() != Some(value)),
+            None => true
+        })
     }
 
     fn check_rows(&self) -> bool {
-        for y in 0..9 {
-            let row : HashSet<_> = HashSet::from_iter(self.get_row(y));
+        for y in 0..self.size() {
+            let row : BTreeSet<_> = BTreeSet::from_iter(self.get_row(y));
             if !self.check_if_points_have_all_digits(&row) {
                 return false;
             }
@@ -160,8 +1043,8 @@ impl SudokuSolver {
     }
 
     fn check_columns(&self) -> bool {
-        for x in 0..9 {
-            let column: HashSet<_> = HashSet::from_iter(self.get_column(x));
+        for x in 0..self.size() {
+            let column: BTreeSet<_> = BTreeSet::from_iter(self.get_column(x));
             if !self.check_if_points_have_all_digits(&column) {
                 return false;
             }
@@ -171,100 +1054,272 @@ impl SudokuSolver {
 
     // region is the small 3x3 square (according to some site with sudoku terminology)
     fn check_regions(&self) -> bool {
-        for y in [0, 3, 6]{
-            for x in [0, 3, 6]{
-                let region = self.get_region(Point::new(x, y));
-                if !self.check_if_points_have_all_digits(&region) {
-                    return false;
-                }
+        for region in self.get_regions() {
+            if !self.check_if_points_have_all_digits(&region) {
+                return false;
             }
         }
         true
     }
 
-    fn get_region(&self, point: Point<usize>) -> HashSet<Point<usize>> {
-        let mut relatives = HashSet::with_capacity(9);
-        let region_coords = self.get_region_coords(point);
-        for y in region_coords.y..region_coords.y + 3 {
-            for x in region_coords.x..region_coords.x + 3 {
+    fn check_extra_regions(&self) -> bool {
+        self.extra_regions.iter().all(|region| self.check_if_points_have_all_digits(region))
+    }
+
+    /// Every row from the top, then every column from the left and then every region in the order of `region_index`,
+    /// the 27 units of a regular board. The extra regions of variants are not included
+    pub fn all_units(&self) -> impl Iterator<Item = BTreeSet<Point<usize>>> + '_ {
+        (0..self.size()).map(|y| self.get_row(y))
+            .chain((0..self.size()).map(|x| self.get_column(x)))
+            .chain(self.get_regions())
+    }
+
+    /// every region of the board from left to right and then top to bottom
+    fn get_regions(&self) -> Vec<BTreeSet<Point<usize>>> {
+        let mut regions = Vec::with_capacity(self.size());
+        for y in (0..self.size()).step_by(self.box_height) {
+            for x in (0..self.size()).step_by(self.box_width) {
+                regions.push(self.get_region(Point::new(x, y)));
+            }
+        }
+        regions
+    }
+
+    fn get_region(&self, point: Point<usize>) -> BTreeSet<Point<usize>> {
+        Self::rectangle_region(self.get_region_coords(point), self.box_width, self.box_height)
+    }
+
+    /// 3x3 square of points with `top_left` as its top left corner
+    fn square_region(top_left: Point<usize>) -> BTreeSet<Point<usize>> {
+        Self::rectangle_region(top_left, 3, 3)
+    }
+
+    fn rectangle_region(top_left: Point<usize>, width: usize, height: usize) -> BTreeSet<Point<usize>> {
+        let mut relatives = BTreeSet::new();
+        for y in top_left.y..top_left.y + height {
+            for x in top_left.x..top_left.x + width {
                 relatives.insert(Point::new(x, y));
             }
         }
         relatives
     }
 
-    fn get_row(&self, y : usize) -> HashSet<Point<usize>> {
-        let mut relatives = HashSet::with_capacity(9);
-        for x in 0..9 {
+    fn get_row(&self, y : usize) -> BTreeSet<Point<usize>> {
+        let mut relatives = BTreeSet::new();
+        for x in 0..self.size() {
             relatives.insert(Point::new(x, y));
         }
         relatives
     }
 
-    fn get_column(&self, x : usize) -> HashSet<Point<usize>> {
-        let mut relatives = HashSet::with_capacity(9);
-        for y in 0..9 {
+    fn get_column(&self, x : usize) -> BTreeSet<Point<usize>> {
+        let mut relatives = BTreeSet::new();
+        for y in 0..self.size() {
             relatives.insert(Point::new(x, y));
         }
         relatives
     }
 
-    fn check_if_points_have_all_digits(&self, hash: &HashSet<Point<usize>>) -> bool {
-        self.check_if_hash_has_all_digits(self.points_to_digits(hash))
+    fn check_if_points_have_all_digits(&self, hash: &BTreeSet<Point<usize>>) -> bool {
+        self.missing_digits_in_unit(hash).is_empty()
     }
 
+    /// True if a collapsed cell of `unit` holds `digit`, stops at the first one without collecting the other digits
+    pub fn unit_contains_digit(&self, unit: &BTreeSet<Point<usize>>, digit: u8) -> bool {
+        unit.iter().any(|point| self.get_cell(point).value() == Some(digit))
+    }
 
-    fn points_to_digits(&self, points: &HashSet<Point<usize>>) -> HashSet<u8> {
-        let mut digits = HashSet::with_capacity(points.len());
-        for point in points {
-            if let Cell::Collapsed(value) = self.get_cell(point) {
-                digits.insert(*value);
-            } else {
-                digits.insert(0);
+    /// The digits from 1 up to the size of the board that no collapsed cell of `unit` holds yet, i.e. what is left
+    /// to place in it. A unit has as many cells as there are digits, so this is only empty for a complete unit without duplicates
+    pub fn missing_digits_in_unit(&self, unit: &BTreeSet<Point<usize>>) -> BTreeSet<u8> {
+        let mut digits: BTreeSet<u8> = (1..=self.size() as u8).collect();
+        for point in unit {
+            if let Some(value) = self.get_cell(point).value() {
+                digits.remove(&value);
             }
         }
-
         digits
     }
 
-    fn check_if_hash_has_all_digits(&self, hash: HashSet<u8>) -> bool {
-        let mut digits: HashSet<u8> = HashSet::from([1, 2, 3, 4, 5, 6, 7, 8, 9]);
-        for digit in hash.iter() {
-            if !digits.remove(digit) {
-                return false;
+    /// Same output as `Display` but with `placeholder` printed in place of empty cells,
+    /// e.g. `'.'` or `'0'` when the output is meant to be read back by a parser
+    pub fn to_string_with(&self, placeholder: char) -> String {
+        let mut output = String::new();
+        self.write_board(&mut output, placeholder).expect("writing to a String cannot fail");
+        output
+    }
+
+    /// Every cell in row-major order on a single line with `0` for uncollapsed cells, 81 characters for a 9x9 board
+    pub fn to_flat_string(&self) -> String {
+        self.board.iter().flatten()
+            .map(|cell| cell.value().map_or('0', digit_to_char))
+            .collect()
+    }
+
+    /// Displays as `to_flat_string` instead of the multi-line grid
+    pub fn display_compact(&self) -> CompactDisplay<'_> {
+        CompactDisplay(self)
+    }
+
+    /// Displays the board with the number of candidates left in brackets in place of each empty cell,
+    /// e.g. ` 3  [2]  9  |  1  [3]  5  | ...`. Digits are padded to the width of a count so the columns line up,
+    /// and there is a blank line between bands
+    pub fn display_candidate_counts(&self) -> CandidateCountDisplay<'_> {
+        CandidateCountDisplay(self)
+    }
+
+    /// The board as a GitHub flavored Markdown table with empty cells left blank.
+    /// Markdown tables need a header so the first row is blank, and the borders between regions are
+    /// extra rows and columns of bold `—`, `|` and `+` since Markdown has no way to style the cell borders
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let sudoku = SudokuSolver::with_box_dimensions(vec![
+    ///     vec![1, 0, 3, 0],
+    ///     vec![0, 4, 0, 2],
+    ///     vec![2, 0, 4, 0],
+    ///     vec![0, 3, 0, 1]
+    /// ], 2, 2).unwrap();
+    /// assert_eq!(sudoku.to_markdown(), "\
+    /// |   |   |   |   |   |
+    /// |:-:|:-:|:-:|:-:|:-:|
+    /// | 1 |   | **\\|** | 3 |   |
+    /// |   | 4 | **\\|** |   | 2 |
+    /// | **—** | **—** | **+** | **—** | **—** |
+    /// | 2 |   | **\\|** | 4 |   |
+    /// |   | 3 | **\\|** |   | 1 |
+    /// ");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let size = self.size();
+        let columns = size + size / self.box_width - 1;
+        let is_border_column = |column: usize| column % (self.box_width + 1) == self.box_width;
+
+        let mut output = "|   ".repeat(columns) + "|\n" + &"|:-:".repeat(columns) + "|\n";
+        for y in 0..size {
+            if y != 0 && y % self.box_height == 0 {
+                for column in 0..columns {
+                    output += if is_border_column(column) { "| **+** " } else { "| **—** " };
+                }
+                output += "|\n";
+            }
+            let mut x = 0;
+            for column in 0..columns {
+                if is_border_column(column) {
+                    output += "| **\\|** ";
+                    continue;
+                }
+                match self.get_cell(&Point::new(x, y)).value() {
+                    Some(value) => output += &format!("| {} ", digit_to_char(value)),
+                    None => output += "|   "
+                }
+                x += 1;
             }
+            output += "|\n";
         }
-        digits.is_empty()
+        output
+    }
+
+    fn write_board(&self, f: &mut impl core::fmt::Write, placeholder: char) -> core::fmt::Result {
+        write_grid(f, self.box_width, self.box_height, |point| self.get_cell(&point).value(), placeholder)
     }
 
 }
 
-impl std::fmt::Display for SudokuSolver {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (y, row) in self.board.iter().enumerate() {
-            for (x, cell) in row.iter().enumerate() {
-                match cell {
-                    Cell::Collapsed(value) => write!(f, "{}", value)?,
-                    Cell::Uncollapsed(_) => write!(f, " ")?
-                }
-                write!(f, " ")?;
-                if x % 3 == 2 && x != row.len() - 1 {
-                    write!(f, "| ")?;
-                }
+impl core::fmt::Display for SudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_board(f, ' ')
+    }
+}
+
+/// see `SudokuSolver::display_compact`
+#[derive(Debug, Clone, Copy)]
+pub struct CompactDisplay<'a>(&'a SudokuSolver);
+
+impl core::fmt::Display for CompactDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0.to_flat_string())
+    }
+}
+
+/// see `SudokuSolver::display_candidate_counts`
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateCountDisplay<'a>(&'a SudokuSolver);
+
+impl core::fmt::Display for CandidateCountDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let solver = self.0;
+        write_wide_grid(f, solver.box_width, solver.box_height, |f, point| match solver.get_cell(&point) {
+            Cell::Collapsed(value) => write!(f, " {} ", digit_to_char(*value)),
+            cell => write!(f, "[{}]", cell.get_entropy())
+        })
+    }
+}
+
+/// Writes a grid where `write_cell` writes every cell 3 characters wide, which leaves no room for lines between bands
+/// so they are separated by a blank line instead
+fn write_wide_grid<F>(f: &mut F, box_width: usize, box_height: usize, write_cell: impl Fn(&mut F, Point<usize>) -> core::fmt::Result) -> core::fmt::Result
+    where F: core::fmt::Write
+{
+    let size = box_width * box_height;
+    for y in 0..size {
+        if y != 0 && y % box_height == 0 {
+            writeln!(f)?;
+        }
+        for x in 0..size {
+            if x != 0 {
+                write!(f, "{}", if x % box_width == 0 { " | " } else { " " })?;
             }
+            write_cell(f, Point::new(x, y))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
 
-            write!(f, "\n")?;
-            if y % 3 == 2 && y != self.board.len() - 1 {
-                for x in 0..(2*row.len() + 3) {
-                    if x == 6 || x == 14 {
-                        write!(f, "+")?;
-                    } else {
-                        write!(f, "-")?;
-                    }
+/// highest side length a board can have, digits above 9 are displayed as letters up to `P` for 25
+const MAX_SIZE: usize = 25;
+
+/// Writes a board with regions `box_width` cells wide and `box_height` cells high, `value` gives the digit of a cell
+/// and cells without one are written as `placeholder`
+fn write_grid(f: &mut impl core::fmt::Write, box_width: usize, box_height: usize, value: impl Fn(Point<usize>) -> Option<u8>, placeholder: char) -> core::fmt::Result {
+    let size = box_width * box_height;
+    for y in 0..size {
+        for x in 0..size {
+            match value(Point::new(x, y)) {
+                Some(value) => write!(f, "{}", digit_to_char(value))?,
+                None => write!(f, "{}", placeholder)?
+            }
+            write!(f, " ")?;
+            if x % box_width == box_width - 1 && x != size - 1 {
+                write!(f, "| ")?;
+            }
+        }
+
+        writeln!(f)?;
+        if y % box_height == box_height - 1 && y != size - 1 {
+            // every region takes 2 characters per cell and the "| " between regions adds 2 more
+            let region_width = 2 * box_width + 2;
+            for x in 0..(2 * size + 2 * (size / box_width) - 3) {
+                if x % region_width == region_width - 2 {
+                    write!(f, "+")?;
+                } else {
+                    write!(f, "-")?;
                 }
-                write!(f, "\n")?;
             }
+            writeln!(f)?;
         }
-        Ok(())
     }
-}
\ No newline at end of file
+    Ok(())
+}
+
+fn digit_to_char(value: u8) -> char {
+    char::from_digit(value as u32, MAX_SIZE as u32 + 1).unwrap_or('?').to_ascii_uppercase()
+}
+
+/// true if every domain can get a different digit, domains and `used` (the digits that are already taken) are bitmasks
+fn can_assign_distinct(domains: &[u16], used: u16) -> bool {
+    let Some((first, rest)) = domains.split_first() else { return true };
+    (1..=9).any(|digit| first & !used & (1 << digit) != 0 && can_assign_distinct(rest, used | (1 << digit)))
+}
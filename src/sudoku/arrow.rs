@@ -0,0 +1,96 @@
+use crate::point::Point;
+use super::{ErrorSudokuContainsAContradiction, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::vec::Vec;
+
+/// The digit in `circle` equals the sum of the digits in `cells`, digits may repeat along the arrow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrow {
+    pub circle: Point<usize>,
+    pub cells: Vec<Point<usize>>
+}
+
+/// Arrow sudoku, every arrow has to add up to the digit in its circle
+#[derive(Debug, Clone)]
+pub struct ArrowSudokuSolver {
+    solver: SudokuSolver,
+    arrows: Vec<Arrow>
+}
+
+impl ArrowSudokuSolver {
+    pub fn new(starting_state: [[u8; 9]; 9], arrows: Vec<Arrow>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Ok(ArrowSudokuSolver {
+            solver: SudokuSolver::new(starting_state)?,
+            arrows
+        })
+    }
+
+    pub fn solver(&self) -> &SudokuSolver {
+        &self.solver
+    }
+
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        let arrows = self.arrows.clone();
+        self.solver.solve_with_strategy(&mut |solver| apply_arrow_constraints(solver, &arrows).map(|_| ()))
+    }
+
+    /// Bounds the circle by the smallest and largest possible sums of its arrow, and every arrow cell
+    /// by the circle minus the smallest and largest possible sums of the other arrow cells,
+    /// returns how many candidates were removed
+    pub fn apply_arrow_constraints(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        apply_arrow_constraints(&mut self.solver, &self.arrows).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.solver.check_if_correct() && self.arrows.iter().all(|arrow| {
+            let sum: Option<u32> = arrow.cells.iter().map(|cell| self.solver.get_cell(cell).value().map(u32::from)).sum();
+            sum.is_some() && sum == self.solver.get_cell(&arrow.circle).value().map(u32::from)
+        })
+    }
+}
+
+impl core::fmt::Display for ArrowSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.solver.fmt(f)
+    }
+}
+
+fn apply_arrow_constraints(solver: &mut SudokuSolver, arrows: &[Arrow]) -> Result<usize, ()> {
+    let mut removed = 0;
+    for arrow in arrows {
+        let bounds: Vec<(u32, u32)> = arrow.cells.iter().map(|cell| candidate_bounds(solver, cell)).collect();
+        let min_sum: u32 = bounds.iter().map(|(min, _)| min).sum();
+        let max_sum: u32 = bounds.iter().map(|(_, max)| max).sum();
+
+        for value in solver.get_candidates(&arrow.circle) {
+            if (value as u32) < min_sum || (value as u32) > max_sum {
+                solver.get_cell_mut(&arrow.circle).remove(value)?;
+                removed += 1;
+            }
+        }
+
+        let (circle_min, circle_max) = candidate_bounds(solver, &arrow.circle);
+        for (i, cell) in arrow.cells.iter().enumerate() {
+            let (min, max) = bounds[i];
+            let others_min = min_sum - min;
+            let others_max = max_sum - max;
+            for value in solver.get_candidates(cell) {
+                let value = value as u32;
+                if value + others_min > circle_max || value + others_max < circle_min {
+                    solver.get_cell_mut(cell).remove(value as u8)?;
+                    removed += 1;
+                }
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// smallest and largest candidate of the cell, an empty cell gets bounds that no value can fit
+fn candidate_bounds(solver: &SudokuSolver, cell: &Point<usize>) -> (u32, u32) {
+    let candidates = solver.get_candidates(cell);
+    match (candidates.iter().min(), candidates.iter().max()) {
+        (Some(min), Some(max)) => (*min as u32, *max as u32),
+        _ => (10, 0)
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "tokio")]
+use super::{SolveConfig, SudokuError, SudokuSolver};
+#[cfg(feature = "tokio")]
+use tokio_util::sync::CancellationToken;
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Stops a solve through `SolveConfig::cancel` from another thread or task, all clones share the same state.
+/// `solve_async` takes the `CancellationToken` of tokio-util instead and cancels one of these with it
+#[derive(Debug, Clone, Default)]
+pub struct SolveCancel(Arc<AtomicBool>);
+
+impl SolveCancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// every solve using this token or one of its clones gives up with `SudokuError::Cancelled` at its next iteration
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl SudokuSolver {
+    /// Solves like `solve` on the blocking thread pool of tokio, so awaiting it does not hold up the runtime.
+    /// Cancelling `cancel`, or dropping the future before it is done, stops the solve at its next iteration.
+    /// The board is only updated once solving succeeds, on an error it is left as it was
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SudokuError, SudokuSolver};
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let mut sudoku = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    /// let cancel = CancellationToken::new();
+    /// cancel.cancel();
+    /// assert_eq!(sudoku.solve_async(cancel).await, Err(SudokuError::Cancelled));
+    /// assert!(!sudoku.is_solved());
+    ///
+    /// sudoku.solve_async(CancellationToken::new()).await.unwrap();
+    /// assert!(sudoku.check_if_correct());
+    /// # }
+    /// ```
+    pub async fn solve_async(&mut self, cancel: CancellationToken) -> Result<(), SudokuError> {
+        let stop = StopOnDrop(SolveCancel::new());
+        let config = SolveConfig { cancel: Some(stop.0.clone()), ..SolveConfig::default() };
+        let mut solver = self.clone();
+        let mut solving = tokio::task::spawn_blocking(move || solver.solve_with_report(&config).map(|_| solver));
+
+        let solved = tokio::select! {
+            solved = &mut solving => solved,
+            _ = cancel.cancelled() => {
+                stop.0.cancel();
+                solving.await
+            }
+        };
+        *self = match solved {
+            Ok(solved) => solved?,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            // the runtime is shutting down
+            Err(_) => return Err(SudokuError::Cancelled)
+        };
+        Ok(())
+    }
+}
+
+/// cancels the token of a solve once the future waiting for it is dropped, so the blocking thread is not kept busy
+#[cfg(feature = "tokio")]
+struct StopOnDrop(SolveCancel);
+
+#[cfg(feature = "tokio")]
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
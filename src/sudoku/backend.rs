@@ -0,0 +1,80 @@
+use super::{BitmaskSolver, Board, DlxSolver, SolveConfig, SolveReport, SudokuError, SudokuSolver};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// An engine that solves a regular 9x9 board, so callers such as the CLI's `--strategy` can switch between engines
+/// without knowing which one they have. Every engine has to find the same solution for a puzzle with a unique one.
+/// Only `WfcSolver` applies the techniques of `SolveConfig::pipeline`, the other engines ignore it:
+///
+/// ```
+/// use sudoku_solver_cli::sudoku::{Board, SolveConfig, Solver, SudokuError, SudokuSolver};
+/// use std::time::Duration;
+///
+/// let puzzle: Board = "000000080680470020019508647060900004342680000190050830000720403006005010003891500".parse().unwrap();
+/// let solution: Board = "734162985685479321219538647568913274342687159197254836851726493926345718473891562".parse().unwrap();
+/// let contradiction: Board = "110000000000000000000000000000000000000000000000000000000000000000000000000000000".parse().unwrap();
+/// let hard: Vec<Board> = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/puzzles/hard.sdk")).lines()
+///     .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+///     .map(|line| line.parse().unwrap())
+///     .collect();
+/// let expected: Vec<Board> = hard.iter()
+///     .map(|puzzle| SudokuSolver::from_board(*puzzle).unwrap().into_solution().unwrap().into())
+///     .collect();
+/// let timeout = SolveConfig { timeout: Some(Duration::ZERO), ..SolveConfig::default() };
+///
+/// for (name, mut solver) in <dyn Solver>::all() {
+///     let mut board = puzzle;
+///     solver.solve(&mut board, &SolveConfig::default()).unwrap();
+///     assert_eq!(board, solution, "{}", name);
+///
+///     for (i, (puzzle, solution)) in hard.iter().zip(&expected).enumerate() {
+///         let mut board = *puzzle;
+///         solver.solve(&mut board, &SolveConfig::default()).unwrap();
+///         assert_eq!(board, *solution, "{} on hard puzzle {}", name, i);
+///
+///         // giving up on a limit leaves the board as it was
+///         let mut board = *puzzle;
+///         assert_eq!(solver.solve(&mut board, &timeout).unwrap_err(), SudokuError::Timeout, "{} on hard puzzle {}", name, i);
+///         assert_eq!(board, *puzzle, "{} on hard puzzle {}", name, i);
+///     }
+///
+///     let mut board = contradiction;
+///     let error = solver.solve(&mut board, &SolveConfig::default()).unwrap_err();
+///     assert!(matches!(error, SudokuError::InitialContradiction(_)), "{}", name);
+///     assert_eq!(board, contradiction, "{}", name);
+/// }
+/// ```
+pub trait Solver {
+    /// Solves `board` in place, it is only changed when solving succeeds
+    fn solve(&mut self, board: &mut Board, config: &SolveConfig) -> Result<SolveReport, SudokuError>;
+}
+
+impl dyn Solver {
+    /// Every engine with the name `--strategy` selects it by
+    pub fn all() -> Vec<(&'static str, Box<dyn Solver>)> {
+        Vec::from([
+            ("wfc", Box::new(WfcSolver) as Box<dyn Solver>),
+            ("dlx", Box::new(DlxSolver)),
+            ("bitmask", Box::new(BitmaskSolver))
+        ])
+    }
+
+    /// The engine `--strategy` selects with `name`, see `all`
+    pub fn by_name(name: &str) -> Option<Box<dyn Solver>> {
+        Self::all().into_iter().find(|(engine, _)| *engine == name).map(|(_, solver)| solver)
+    }
+}
+
+/// The engine of `SudokuSolver`, which collapses the cell with the fewest candidates and backtracks on contradictions
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WfcSolver;
+
+impl Solver for WfcSolver {
+    fn solve(&mut self, board: &mut Board, config: &SolveConfig) -> Result<SolveReport, SudokuError> {
+        let mut solver = SudokuSolver::from_board(*board)?;
+        let report = solver.solve_with_report(config)?;
+        *board = solver.into_board();
+        Ok(report)
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Board, SolveConfig, SolveReport, SudokuError, SudokuSolver};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+// boards are solved on other threads
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<SudokuSolver>();
+};
+
+/// A board solved by `SudokuSolver::solve_many`
+#[derive(Debug, Clone)]
+pub struct SolveOutcome {
+    pub solution: Board,
+    pub report: SolveReport
+}
+
+impl SudokuSolver {
+    /// Solves every board with `config`, spread over one thread per available core.
+    /// The results are in the same order as `boards` and the same as solving them one after another,
+    /// a timeout or backtrack limit applies to each board on its own
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{Board, SolveConfig, SudokuSolver};
+    ///
+    /// let solution = SudokuSolver::generate(1);
+    /// let mut solved = SudokuSolver::new(solution).unwrap();
+    /// solved.solve().unwrap();
+    /// let solution = solved.as_array();
+    /// let boards: Vec<Board> = (0..200).map(|i| {
+    ///     let mut cells = solution;
+    ///     for (j, cell) in cells.iter_mut().flatten().enumerate() {
+    ///         if (j * 7 + i) % 3 == 0 {
+    ///             *cell = 0;
+    ///         }
+    ///     }
+    ///     Board::new(cells)
+    /// }).collect();
+    ///
+    /// let serial: Vec<_> = boards.iter().map(|board| {
+    ///     SudokuSolver::from_board(*board).unwrap().into_solution().map(Board::new)
+    /// }).collect();
+    /// let parallel: Vec<_> = SudokuSolver::solve_many(boards, &SolveConfig::default()).into_iter()
+    ///     .map(|outcome| outcome.map(|outcome| outcome.solution))
+    ///     .collect();
+    /// assert_eq!(serial, parallel);
+    /// ```
+    pub fn solve_many(boards: Vec<Board>, config: &SolveConfig) -> Vec<Result<SolveOutcome, SudokuError>> {
+        let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get()).min(boards.len());
+        let results: Vec<Mutex<Option<Result<SolveOutcome, SudokuError>>>> = boards.iter().map(|_| Mutex::new(None)).collect();
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    // every board shares the peers of this one instead of computing its own
+                    let template = SudokuSolver::new([[0; 9]; 9]).expect("an empty board cannot contain a contradiction");
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(board) = boards.get(i) else { break };
+                        *results[i].lock().unwrap() = Some(solve_board(&template, *board, config));
+                    }
+                });
+            }
+        });
+        results.into_iter()
+            .map(|result| result.into_inner().unwrap().expect("every board is solved before the threads finish"))
+            .collect()
+    }
+}
+
+fn solve_board(template: &SudokuSolver, board: Board, config: &SolveConfig) -> Result<SolveOutcome, SudokuError> {
+    let rows: Vec<Vec<u8>> = board.as_array().iter().map(|row| row.to_vec()).collect();
+    let mut solver = template.with_values(&rows).map_err(|contradiction| SudokuError::InitialContradiction(Some(contradiction)))?;
+    let report = solver.solve_with_report(config)?;
+    Ok(SolveOutcome { solution: solver.into_board(), report })
+}
@@ -0,0 +1,145 @@
+use super::{Board, SolveConfig, SolveReport, Solver, SudokuError};
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// The simplest engine, selected with `--strategy bitmask`: a recursive search over the 81 digits that keeps the digits
+/// used by every row, column and region as bitmasks and fills the empty cell with the fewest digits left first.
+/// There are no candidates or techniques and nothing is allocated while searching, so it is a baseline for the other
+/// engines to be measured against with `bench --strategy` and a reference to check their solutions with.
+/// Of `SolveConfig` only `timeout` and `cancel` are used
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BitmaskSolver;
+
+impl Solver for BitmaskSolver {
+    fn solve(&mut self, board: &mut Board, config: &SolveConfig) -> Result<SolveReport, SudokuError> {
+        board.check_givens()?;
+        let mut grid = Grid::new(board);
+        let mut search = Search {
+            config,
+            #[cfg(feature = "std")]
+            start: Instant::now(),
+            report: SolveReport::default()
+        };
+        if !grid.search(&mut search, 0)? {
+            return Err(SudokuError::Unsolvable);
+        }
+        let mut cells = [[0; 9]; 9];
+        for (i, digit) in grid.cells.iter().enumerate() {
+            cells[i / 9][i % 9] = *digit;
+        }
+        *board = Board::new(cells);
+        #[cfg(feature = "std")]
+        {
+            search.report.elapsed = search.start.elapsed();
+        }
+        Ok(search.report)
+    }
+}
+
+/// the bits of the digits 1 to 9
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+/// The digits of the board in row-major order, 0 for empty cells, and which digits every unit already holds,
+/// bit `d` being set if digit `d` is used
+#[derive(Debug, Clone)]
+struct Grid {
+    cells: [u8; 81],
+    rows: [u16; 9],
+    columns: [u16; 9],
+    regions: [u16; 9]
+}
+
+/// what stays the same during a search
+struct Search<'a> {
+    config: &'a SolveConfig,
+    #[cfg(feature = "std")]
+    start: Instant,
+    report: SolveReport
+}
+
+impl Grid {
+    /// `board` has to pass `Board::check_givens`
+    fn new(board: &Board) -> Self {
+        let mut grid = Grid { cells: [0; 81], rows: [0; 9], columns: [0; 9], regions: [0; 9] };
+        for (i, digit) in board.as_array().iter().flatten().enumerate() {
+            if *digit != 0 {
+                grid.place(i, *digit);
+            }
+        }
+        grid
+    }
+
+    fn place(&mut self, cell: usize, digit: u8) {
+        let bit = 1 << digit;
+        self.cells[cell] = digit;
+        self.rows[cell / 9] |= bit;
+        self.columns[cell % 9] |= bit;
+        self.regions[region(cell)] |= bit;
+    }
+
+    fn clear(&mut self, cell: usize, digit: u8) {
+        let bit = !(1 << digit);
+        self.cells[cell] = 0;
+        self.rows[cell / 9] &= bit;
+        self.columns[cell % 9] &= bit;
+        self.regions[region(cell)] &= bit;
+    }
+
+    /// the digits none of the units of `cell` hold yet
+    fn free(&self, cell: usize) -> u16 {
+        ALL_DIGITS & !(self.rows[cell / 9] | self.columns[cell % 9] | self.regions[region(cell)])
+    }
+
+    /// Fills the empty cells, true once the board is full and false if it cannot be filled from here
+    fn search(&mut self, search: &mut Search, depth: usize) -> Result<bool, SudokuError> {
+        #[cfg(feature = "std")]
+        if search.config.timeout.is_some_and(|timeout| search.start.elapsed() > timeout) {
+            return Err(SudokuError::Timeout);
+        }
+        if search.config.cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled()) {
+            return Err(SudokuError::Cancelled);
+        }
+
+        let mut best = None::<(usize, u16)>;
+        for cell in (0..81).filter(|cell| self.cells[*cell] == 0) {
+            let free = self.free(cell);
+            if best.is_none_or(|(_, best)| free.count_ones() < best.count_ones()) {
+                best = Some((cell, free));
+            }
+            if free == 0 {
+                break;
+            }
+        }
+        let Some((cell, mut free)) = best else {
+            search.report.leaves_reached += 1;
+            return Ok(true);
+        };
+        if free == 0 {
+            search.report.leaves_reached += 1;
+            return Ok(false);
+        }
+        if free.count_ones() > 1 {
+            search.report.guesses += 1;
+        }
+
+        while free != 0 {
+            let digit = free.trailing_zeros() as u8;
+            free &= free - 1;
+            self.place(cell, digit);
+            search.report.nodes_visited += 1;
+            search.report.max_depth = search.report.max_depth.max(depth + 1);
+            if self.search(search, depth + 1)? {
+                return Ok(true);
+            }
+            self.clear(cell, digit);
+            search.report.backtracks += 1;
+        }
+        Ok(false)
+    }
+}
+
+/// the region of the cell with index `cell` in row-major order
+fn region(cell: usize) -> usize {
+    cell / 27 * 3 + cell % 9 / 3
+}
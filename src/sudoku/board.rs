@@ -0,0 +1,128 @@
+use crate::parse::{self, ParseError};
+use crate::point::Point;
+use super::{digit_to_char, write_grid, write_wide_grid, Conflict, Contradiction, ErrorSudokuContainsAContradiction, SolveConfig, SudokuError, SudokuSolver};
+
+use alloc::collections::BTreeSet;
+
+/// The digits of a 9x9 sudoku without any of the solving state, cheap to copy and compare.
+/// 0 is an empty cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Board([[u8; 9]; 9]);
+
+impl Board {
+    pub fn new(cells: [[u8; 9]; 9]) -> Self {
+        Board(cells)
+    }
+
+    /// the digit in the cell, 0 if it is empty
+    pub fn get(&self, point: Point<usize>) -> u8 {
+        self.0[point.y][point.x]
+    }
+
+    pub fn as_array(&self) -> [[u8; 9]; 9] {
+        self.0
+    }
+
+    /// Every digit is at most 9 and no digit appears twice in a row, column or region.
+    /// A valid board can still have no solution
+    pub fn is_valid(&self) -> bool {
+        (0..81).map(|i| Point::from_index(i, 9)).all(|point| {
+            let value = self.get(point);
+            value <= 9 && (value == 0 || SudokuSolver::peers(point).all(|peer| self.get(peer) != value))
+        })
+    }
+
+    /// no empty cells left
+    pub fn is_complete(&self) -> bool {
+        self.0.iter().flatten().all(|value| *value != 0)
+    }
+
+    /// For the engines that do not build a `SudokuSolver`, fails with `SudokuError::InvalidDimensions` if a value is
+    /// above 9 and with `SudokuError::InitialContradiction` if two givens that see each other hold the same digit,
+    /// `first` being the later of them in row-major order
+    pub(super) fn check_givens(&self) -> Result<(), SudokuError> {
+        if self.0.iter().flatten().any(|value| *value > 9) {
+            return Err(SudokuError::InvalidDimensions);
+        }
+        for point in (0..81).map(|i| Point::from_index(i, 9)) {
+            let digit = self.get(point);
+            let earlier = SudokuSolver::peers(point).find(|peer| peer.to_index(9) < point.to_index(9) && self.get(*peer) == digit);
+            if let Some(second) = earlier.filter(|_| digit != 0) {
+                return Err(SudokuError::InitialContradiction(Some(Contradiction::Duplicate { first: point, second, digit })));
+            }
+        }
+        Ok(())
+    }
+
+    /// Displays the board with every cell that is part of a `SudokuSolver::validate` conflict in brackets, e.g. ` 3  [5]  9  | ...`,
+    /// so that it also works for boards no `SudokuSolver` can be created from. Empty cells are left blank
+    pub fn display_conflicts(&self) -> ConflictDisplay<'_> {
+        ConflictDisplay(self)
+    }
+}
+
+/// see `Board::display_conflicts`
+#[derive(Debug, Clone, Copy)]
+pub struct ConflictDisplay<'a>(&'a Board);
+
+impl core::fmt::Display for ConflictDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let conflicting: BTreeSet<Point<usize>> = SudokuSolver::validate(&self.0.0).iter()
+            .flat_map(|conflict| match *conflict {
+                Conflict::OutOfRange { cell, .. } => [Some(cell), None],
+                Conflict::Duplicate { first, second, .. } => [Some(first), Some(second)]
+            })
+            .flatten()
+            .collect();
+        write_wide_grid(f, 3, 3, |f, point| match self.0.get(point) {
+            0 => write!(f, "   "),
+            value if conflicting.contains(&point) => write!(f, "[{}]", digit_to_char(value)),
+            value => write!(f, " {} ", digit_to_char(value))
+        })
+    }
+}
+
+impl From<[[u8; 9]; 9]> for Board {
+    fn from(cells: [[u8; 9]; 9]) -> Self {
+        Board(cells)
+    }
+}
+
+impl From<Board> for [[u8; 9]; 9] {
+    fn from(board: Board) -> Self {
+        board.0
+    }
+}
+
+/// any of the formats `parse::from_flexible` accepts
+impl core::str::FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::from_flexible(s).map(Board)
+    }
+}
+
+/// the same grid as the `Display` of `SudokuSolver` with empty cells left blank
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_grid(f, 3, 3, |point| Some(self.get(point)).filter(|value| *value != 0), ' ')
+    }
+}
+
+impl SudokuSolver {
+    pub fn from_board(board: Board) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        SudokuSolver::new(board.0)
+    }
+
+    /// the digits of the current board without the solving state, uncollapsed cells are empty
+    pub fn into_board(self) -> Board {
+        Board(self.as_array())
+    }
+
+    /// Solves the board unless it already is solved and hands back only the solution
+    pub fn into_solution(mut self) -> Result<[[u8; 9]; 9], SudokuError> {
+        self.solve_with_report(&SolveConfig::default())?;
+        Ok(self.as_array())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::point::Point;
+use super::{SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::{collections::{BTreeMap, BTreeSet, VecDeque}, vec, vec::Vec};
+
+impl SudokuSolver {
+    /// Applies simple coloring, chains of a single digit, until it finds nothing more and returns how many candidates
+    /// that removed. Two cells are a conjugate pair of a digit when they are the only cells of a row, column or region
+    /// that can hold it, so exactly one of them does. The pairs join into chains whose cells are colored in turns,
+    /// and one color holds the digit while the other does not. Then
+    /// - two cells of the same color that see each other cannot both hold it, so no cell of that color does
+    /// - a cell that sees cells of both colors sees the digit either way, so it cannot hold it
+    ///
+    /// Cells left with a single candidate are not collapsed. Fails if removing candidates leaves a cell with none
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// // 1 can only go into r1c1 or r1c5 in row 1, r1c5 or r7c5 in column 5 and r7c5 or r7c2 in row 7,
+    /// // so one of r1c1 and r7c2 is 1 and r2c2, r3c2, r8c1 and r9c1 see both
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// let cells = (0..9).flat_map(|y| (0..9).map(move |x| (y, x)));
+    /// for (y, x) in cells.filter(|(y, x)| (*y == 0 && ![0, 4].contains(x)) || (*y == 6 && ![1, 4].contains(x))
+    ///                                     || (*x == 4 && ![0, 6].contains(y))) {
+    ///     candidates[y][x] = (2..=9).collect();
+    /// }
+    /// let mut sudoku = SudokuSolver::from_candidates(candidates).unwrap();
+    /// assert_eq!(sudoku.apply_simple_coloring().unwrap(), 4);
+    /// assert_eq!([sudoku.entropy_map()[1][1], sudoku.entropy_map()[8][0]], [8, 8]);
+    /// assert_eq!(sudoku.apply_simple_coloring().unwrap(), 0);
+    /// ```
+    pub fn apply_simple_coloring(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        let mut removed = 0;
+        while let Some(eliminated) = (1..=self.size() as u8).find_map(|digit| self.simple_coloring_of(digit)) {
+            for (point, digit) in eliminated {
+                self.get_cell_mut(&point).remove(digit).map_err(|_| SudokuIsUnsolvable)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// the candidates of `digit` removed by its first chain that removes any, chains start at their first cell
+    /// in row-major order
+    fn simple_coloring_of(&self, digit: u8) -> Option<Vec<(Point<usize>, u8)>> {
+        let holds = |point: &Point<usize>| !self.get_cell(point).is_collapsed() && self.get_cell(point).contains(digit);
+
+        // the conjugate pairs of every cell
+        let mut links: BTreeMap<Point<usize>, BTreeSet<Point<usize>>> = BTreeMap::new();
+        for unit in self.all_units().chain(self.extra_regions.iter().cloned()) {
+            if let [first, second] = unit.iter().copied().filter(holds).collect::<Vec<_>>()[..] {
+                links.entry(first).or_default().insert(second);
+                links.entry(second).or_default().insert(first);
+            }
+        }
+
+        let mut colored: BTreeSet<Point<usize>> = BTreeSet::new();
+        let mut starts: Vec<Point<usize>> = links.keys().copied().collect();
+        starts.sort_by_key(|point| (point.y, point.x));
+        starts.into_iter().find_map(|start| {
+            if !colored.insert(start) {
+                return None;
+            }
+            // breadth first, every cell gets the other color than the cell it was reached from
+            let mut colors: [Vec<Point<usize>>; 2] = [vec![start], Vec::new()];
+            let mut queue = VecDeque::from([(start, 0)]);
+            while let Some((cell, color)) = queue.pop_front() {
+                for linked in &links[&cell] {
+                    if colored.insert(*linked) {
+                        colors[1 - color].push(*linked);
+                        queue.push_back((*linked, 1 - color));
+                    }
+                }
+            }
+            let sees = |a: &Point<usize>, b: &Point<usize>| self.get_peers(*a).contains(b);
+
+            let mut eliminated: Vec<(Point<usize>, u8)> = match colors.iter().find(|cells| {
+                cells.iter().enumerate().any(|(i, a)| cells[i + 1..].iter().any(|b| sees(a, b)))
+            }) {
+                Some(wrong) => wrong.iter().map(|cell| (*cell, digit)).collect(),
+                None => {
+                    let size = self.size();
+                    (0..size * size).map(|i| Point::from_index(i, size))
+                        .filter(|point| holds(point) && !colors.iter().any(|cells| cells.contains(point)))
+                        .filter(|point| colors.iter().all(|cells| cells.iter().any(|cell| sees(point, cell))))
+                        .map(|point| (point, digit))
+                        .collect()
+                }
+            };
+            eliminated.sort_by_key(|(point, _)| (point.y, point.x));
+            (!eliminated.is_empty()).then_some(eliminated)
+        })
+    }
+}
@@ -0,0 +1,261 @@
+use crate::point::Point;
+use super::{Board, SolveConfig, SolveReport, Solver, SudokuError};
+
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// An engine that solves the board as an exact cover problem with Knuth's Algorithm X and dancing links,
+/// selected with `--strategy dlx`. Every cell, and every digit of every row, column and region, has to be covered
+/// exactly once, which makes 324 constraints, and each of the 729 ways to put a digit into a cell covers four of them.
+/// The only technique is always branching on the constraint with the fewest options left.
+/// Of `SolveConfig` only `timeout` and `cancel` are used
+///
+/// It finds the same solutions as `WfcSolver`, and counts them much faster:
+/// ```
+/// use sudoku_solver_cli::sudoku::{Board, DlxSolver, SolveConfig, Solver, SudokuSolver, WfcSolver};
+///
+/// for seed in 0..5 {
+///     let puzzle = Board::new(SudokuSolver::generate(seed));
+///     let (mut dlx, mut wfc) = (puzzle, puzzle);
+///     DlxSolver.solve(&mut dlx, &SolveConfig::default()).unwrap();
+///     WfcSolver.solve(&mut wfc, &SolveConfig::default()).unwrap();
+///     assert_eq!(dlx, wfc);
+///
+///     // without its first row a puzzle usually has more than one solution
+///     let mut cells = puzzle.as_array();
+///     cells[0] = [0; 9];
+///     let expected = SudokuSolver::new(cells).unwrap().count_solutions(1000);
+///     assert_eq!(DlxSolver::count_solutions(&Board::new(cells), 1000), Ok(expected));
+/// }
+/// assert_eq!(DlxSolver::count_solutions(&Board::default(), 1000), Ok(1000));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DlxSolver;
+
+impl DlxSolver {
+    /// Counts the solutions of `board`, stopping as soon as `limit` of them are found
+    pub fn count_solutions(board: &Board, limit: usize) -> Result<usize, SudokuError> {
+        let config = SolveConfig::default();
+        let mut search = Search::new(board, &config, limit)?;
+        search.run()?;
+        Ok(search.solutions.len())
+    }
+}
+
+impl Solver for DlxSolver {
+    fn solve(&mut self, board: &mut Board, config: &SolveConfig) -> Result<SolveReport, SudokuError> {
+        let mut search = Search::new(board, config, 1)?;
+        search.run()?;
+        let Some(solution) = search.solutions.first() else { return Err(SudokuError::Unsolvable) };
+        let mut cells = [[0; 9]; 9];
+        for option in solution {
+            let (point, digit) = placement(*option);
+            cells[point.y][point.x] = digit;
+        }
+        *board = Board::new(cells);
+        #[cfg(feature = "std")]
+        {
+            search.report.elapsed = search.start.elapsed();
+        }
+        Ok(search.report)
+    }
+}
+
+/// constraints of a 9x9 sudoku, every cell and every digit in every row, column and region
+const CONSTRAINTS: usize = 324;
+
+/// the cell and digit of option `option`, which puts digit `option % 9 + 1` into cell `option / 9` in row-major order
+fn placement(option: usize) -> (Point<usize>, u8) {
+    (Point::from_index(option / 9, 9), (option % 9 + 1) as u8)
+}
+
+/// The toroidal linked lists of dancing links kept in one arena and linked by index.
+/// Node 0 is the root, the next `CONSTRAINTS` nodes are the column headers and every option adds one node per
+/// constraint it covers
+#[derive(Debug, Clone)]
+struct Links {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// the column header of every node
+    column: Vec<usize>,
+    /// the option every node belongs to, unused for the root and the headers
+    option: Vec<usize>,
+    /// how many options are left in every column, indexed by its header
+    size: Vec<usize>
+}
+
+impl Links {
+    fn new() -> Self {
+        let headers = CONSTRAINTS + 1;
+        Links {
+            left: (0..headers).map(|node| (node + headers - 1) % headers).collect(),
+            right: (0..headers).map(|node| (node + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            option: vec![0; headers],
+            size: vec![0; headers]
+        }
+    }
+
+    /// adds `option` as a row covering the constraints `columns`, given as indices from 0
+    fn add_option(&mut self, option: usize, columns: [usize; 4]) {
+        let first = self.left.len();
+        for (i, constraint) in columns.into_iter().enumerate() {
+            let node = first + i;
+            let header = constraint + 1;
+            self.left.push(if i == 0 { first + columns.len() - 1 } else { node - 1 });
+            self.right.push(if i == columns.len() - 1 { first } else { node + 1 });
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            self.column.push(header);
+            self.option.push(option);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+        }
+    }
+
+    /// removes the column `header` and every option that covers it
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// undoes `cover(header)`, covers have to be undone in the reverse order
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// the uncovered column with the fewest options, None once every column is covered
+    fn smallest_column(&self) -> Option<usize> {
+        let mut best = None::<usize>;
+        let mut header = self.right[0];
+        while header != 0 {
+            if best.is_none_or(|best| self.size[header] < self.size[best]) {
+                best = Some(header);
+            }
+            header = self.right[header];
+        }
+        best
+    }
+}
+
+/// One search for up to `limit` solutions, each one the options it chose
+struct Search<'a> {
+    links: Links,
+    chosen: Vec<usize>,
+    solutions: Vec<Vec<usize>>,
+    limit: usize,
+    config: &'a SolveConfig,
+    #[cfg(feature = "std")]
+    start: Instant,
+    report: SolveReport
+}
+
+impl<'a> Search<'a> {
+    /// Fails like `Board::check_givens`
+    fn new(board: &Board, config: &'a SolveConfig, limit: usize) -> Result<Self, SudokuError> {
+        board.check_givens()?;
+        let mut links = Links::new();
+        for option in 0..729 {
+            let (point, digit) = placement(option);
+            let given = board.get(point);
+            if given != 0 && given != digit {
+                continue;
+            }
+            let region = point.y / 3 * 3 + point.x / 3;
+            let digit = digit as usize - 1;
+            links.add_option(option, [point.to_index(9), 81 + point.y * 9 + digit, 162 + point.x * 9 + digit, 243 + region * 9 + digit]);
+        }
+        Ok(Search {
+            links,
+            chosen: Vec::new(),
+            solutions: Vec::new(),
+            limit,
+            config,
+            #[cfg(feature = "std")]
+            start: Instant::now(),
+            report: SolveReport::default()
+        })
+    }
+
+    /// Algorithm X, returns once `limit` solutions are found or there are no more
+    fn run(&mut self) -> Result<(), SudokuError> {
+        #[cfg(feature = "std")]
+        if self.config.timeout.is_some_and(|timeout| self.start.elapsed() > timeout) {
+            return Err(SudokuError::Timeout);
+        }
+        if self.config.cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled()) {
+            return Err(SudokuError::Cancelled);
+        }
+        let Some(header) = self.links.smallest_column() else {
+            self.report.leaves_reached += 1;
+            self.solutions.push(self.chosen.clone());
+            return Ok(());
+        };
+        if self.links.size[header] == 0 {
+            self.report.leaves_reached += 1;
+            return Ok(());
+        }
+        if self.links.size[header] > 1 {
+            self.report.guesses += 1;
+        }
+
+        self.links.cover(header);
+        let mut row = self.links.down[header];
+        while row != header && self.solutions.len() < self.limit {
+            self.chosen.push(self.links.option[row]);
+            self.report.nodes_visited += 1;
+            self.report.max_depth = self.report.max_depth.max(self.chosen.len());
+            let mut node = self.links.right[row];
+            while node != row {
+                self.links.cover(self.links.column[node]);
+                node = self.links.right[node];
+            }
+
+            let result = self.run();
+
+            let mut node = self.links.left[row];
+            while node != row {
+                self.links.uncover(self.links.column[node]);
+                node = self.links.left[node];
+            }
+            self.chosen.pop();
+            result?;
+            if self.solutions.len() < self.limit {
+                self.report.backtracks += 1;
+            }
+            row = self.links.down[row];
+        }
+        self.links.uncover(header);
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use crate::point::Point;
+use super::{SolveConfig, SudokuSolver, Technique, TechniquePipeline};
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+/// How hard a puzzle is for this solver, going by how many times it has to backtrack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// at most 2 backtracks
+    Easy,
+    /// 3 to 15 backtracks
+    Medium,
+    /// 16 to 60 backtracks
+    Hard,
+    /// more than 60 backtracks
+    Expert
+}
+
+impl core::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+            Difficulty::Expert => write!(f, "expert")
+        }
+    }
+}
+
+/// how `difficulty` and `branching_profile` solve, the bands of `Difficulty` were measured with it
+fn rating_config() -> SolveConfig {
+    SolveConfig { pipeline: TechniquePipeline::new([Technique::NakedSingle]), ..SolveConfig::default() }
+}
+
+impl SudokuSolver {
+    /// Rates the current board by solving a copy of it, None if it has no solution.
+    /// The copy is solved without hidden singles, which avoid most backtracking and would leave little to rate by
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        let report = self.search_copy().run_search(&rating_config(), &mut |_| Ok(())).ok()?;
+        Some(match report.backtracks {
+            0..=2 => Difficulty::Easy,
+            3..=15 => Difficulty::Medium,
+            16..=60 => Difficulty::Hard,
+            _ => Difficulty::Expert
+        })
+    }
+
+    /// `SolveReport::branching` of solving a copy of the current board, empty if it has no solution.
+    /// Many collapses with few candidates make a puzzle easier, guesses between many candidates make it harder
+    pub fn branching_profile(&self) -> Vec<usize> {
+        self.search_copy().run_search(&rating_config(), &mut |_| Ok(()))
+            .map_or_else(|_| Vec::new(), |report| report.branching)
+    }
+
+    /// True if the givens have a unique solution and removing any one of them would allow more solutions.
+    /// Checks uniqueness once per given, so it costs as much as solving the puzzle times the number of givens
+    pub fn is_minimal(&self) -> bool {
+        let size = self.size();
+        let mut givens = vec![vec![0; size]; size];
+        for (point, value) in self.givens() {
+            givens[point.y][point.x] = value;
+        }
+        let has_unique_solution = |values: &[Vec<u8>]| self.with_values(values).is_ok_and(|solver| solver.has_unique_solution());
+        if !has_unique_solution(&givens) {
+            return false;
+        }
+        self.givens().all(|(point, value)| {
+            givens[point.y][point.x] = 0;
+            let essential = !has_unique_solution(&givens);
+            givens[point.y][point.x] = value;
+            essential
+        })
+    }
+
+    /// A puzzle with a unique solution where no clue can be removed without losing uniqueness,
+    /// the same seed always gives the same puzzle
+    pub fn generate(seed: u64) -> [[u8; 9]; 9] {
+        let mut rng = SplitMix64(seed);
+        let solution = random_solution(&mut rng);
+
+        let mut cells: Vec<usize> = (0..81).collect();
+        rng.shuffle(&mut cells);
+        minimize(solution, &cells)
+    }
+
+    /// Up to `max` distinct minimal puzzles whose solution is the current board, for studying the puzzles
+    /// a single solution grid allows. Each one is found by removing clues from the full grid in a different
+    /// random order, which is deterministic, keeping every clue whose removal would allow a second solution.
+    /// Empty if the board is not a complete and correct 9x9 grid.
+    ///
+    /// This is expensive: every puzzle costs one uniqueness check per cell, i.e. 81 solves, and removal orders
+    /// that lead to a puzzle found before are wasted. The search gives up after `4 * max` orders, so fewer than `max`
+    /// puzzles are only returned if most orders keep leading to the same few puzzles
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut solution = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    /// solution.solve().unwrap();
+    /// let puzzles = solution.enumerate_minimal(2);
+    /// assert_eq!(puzzles.len(), 2);
+    /// assert_ne!(puzzles[0], puzzles[1]);
+    /// for puzzle in puzzles {
+    ///     let mut puzzle = SudokuSolver::new(puzzle).unwrap();
+    ///     assert!(puzzle.is_minimal());
+    ///     puzzle.solve().unwrap();
+    ///     assert_eq!(puzzle.as_array(), solution.as_array());
+    /// }
+    /// ```
+    pub fn enumerate_minimal(&self, max: usize) -> Vec<[[u8; 9]; 9]> {
+        if self.size() != 9 || !self.check_if_correct() {
+            return Vec::new();
+        }
+        let solution = self.as_array();
+        let mut rng = SplitMix64(0);
+        let mut found = BTreeSet::new();
+        for _ in 0..max.saturating_mul(4) {
+            if found.len() == max {
+                break;
+            }
+            let mut cells: Vec<usize> = (0..81).collect();
+            rng.shuffle(&mut cells);
+            found.insert(minimize(solution, &cells));
+        }
+        found.into_iter().collect()
+    }
+
+    /// Lazily generates `count` puzzles of `difficulty`, no two of them with the same `canonical_form`.
+    /// Every attempt advances the seed and puzzles of other difficulties are thrown away,
+    /// so the rarer the difficulty the longer each puzzle takes
+    pub fn generate_many(count: usize, difficulty: Difficulty, seed: u64) -> impl Iterator<Item = [[u8; 9]; 9]> {
+        let mut seeds = SplitMix64(seed);
+        let mut seen = BTreeSet::new();
+        core::iter::from_fn(move || loop {
+            let puzzle = SudokuSolver::generate(seeds.next());
+            let rated = SudokuSolver::new(puzzle).ok().and_then(|solver| solver.difficulty());
+            if rated == Some(difficulty) && seen.insert(SudokuSolver::canonical_form(puzzle)) {
+                return Some(puzzle);
+            }
+        }).take(count)
+    }
+
+    /// The same puzzle for every rotation, reflection and relabeling of the digits of `grid`:
+    /// the smallest of its 8 rotations and reflections in row-major order, after relabeling the digits
+    /// of each one in the order they first appear
+    pub fn canonical_form(grid: [[u8; 9]; 9]) -> [[u8; 9]; 9] {
+        let transforms: [Transform; 8] = [
+            |x, y| (x, y),
+            |x, y| (8 - y, x),
+            |x, y| (8 - x, 8 - y),
+            |x, y| (y, 8 - x),
+            |x, y| (8 - x, y),
+            |x, y| (x, 8 - y),
+            |x, y| (y, x),
+            |x, y| (8 - y, 8 - x)
+        ];
+        transforms.iter().map(|transform| {
+            let mut labels = [0u8; 10];
+            let mut next_label = 1;
+            let mut transformed = [[0u8; 9]; 9];
+            for (y, row) in transformed.iter_mut().enumerate() {
+                for (x, cell) in row.iter_mut().enumerate() {
+                    let (source_x, source_y) = transform(x, y);
+                    let value = grid[source_y][source_x] as usize;
+                    if value != 0 && labels[value] == 0 {
+                        labels[value] = next_label;
+                        next_label += 1;
+                    }
+                    *cell = labels[value];
+                }
+            }
+            transformed
+        }).min().expect("there are always 8 transforms")
+    }
+}
+
+/// Removes the clues of `puzzle` in `order`, given as indices in row-major order, skipping every clue
+/// whose removal would allow a second solution. What is left is minimal
+fn minimize(mut puzzle: [[u8; 9]; 9], order: &[usize]) -> [[u8; 9]; 9] {
+    for point in order.iter().map(|i| Point::from_index(*i, 9)) {
+        let value = puzzle[point.y][point.x];
+        puzzle[point.y][point.x] = 0;
+        let unique = SudokuSolver::new(puzzle).is_ok_and(|solver| solver.has_unique_solution());
+        if !unique {
+            puzzle[point.y][point.x] = value;
+        }
+    }
+    puzzle
+}
+
+/// maps a cell of the transformed grid to the cell of the original grid it comes from
+type Transform = fn(usize, usize) -> (usize, usize);
+
+/// A solved grid found by solving an empty board with guesses seeded from `rng`, so any grid can come out
+fn random_solution(rng: &mut SplitMix64) -> [[u8; 9]; 9] {
+    let mut solution = SudokuSolver::new([[0; 9]; 9]).expect("an empty board cannot contain a contradiction");
+    let config = SolveConfig { seed: Some(rng.next()), ..SolveConfig::default() };
+    solution.solve_with_report(&config).expect("an empty board always has a solution");
+    solution.as_array()
+}
+
+/// Small deterministic random number generator so generating does not need a dependency
+#[derive(Debug, Clone)]
+pub(super) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// the generator of iteration `iteration` of a search seeded with `seed`, see `SolveConfig::seed`.
+    /// The seed is mixed first so that neighbouring seeds do not make the same guesses one iteration apart
+    pub(super) fn for_iteration(seed: u64, iteration: usize) -> Self {
+        SplitMix64(SplitMix64(seed).next() ^ iteration as u64)
+    }
+
+    pub(super) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Fisher-Yates
+    pub(super) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
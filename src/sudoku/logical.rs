@@ -0,0 +1,167 @@
+use crate::cell::Cell;
+use crate::point::Point;
+use super::{Contradiction, SolveConfig, SolveReport, SudokuSolver, Technique, TechniquePipeline, TechniqueSet};
+
+use alloc::string::ToString;
+
+/// How far `SudokuSolver::solve_logical` got without guessing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicalOutcome {
+    /// every cell was filled without a single guess
+    Solved,
+    /// the techniques ran out before the board was full, `board` is what they filled in with `0` for the empty cells
+    /// and `candidates` is `SudokuSolver::entropy_map` of it
+    Stalled { board: [[u8; 9]; 9], candidates: [[u8; 9]; 9] },
+    /// the techniques ran into a contradiction, so the puzzle has no solution
+    Unsolvable(Contradiction)
+}
+
+impl SudokuSolver {
+    /// Fills in everything the techniques force and stops there instead of guessing,
+    /// so whether the puzzle gets solved tells if a human can solve it without trial and error.
+    /// The board is left where the techniques stopped. Only for 9x9 boards
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{LogicalOutcome, SudokuSolver};
+    ///
+    /// let solvable: Vec<u8> = "200000800005140003000000000030860007900400600000005100090370000076020000001009500"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let mut solvable = SudokuSolver::from_flat(&solvable).unwrap();
+    /// assert_eq!(solvable.solve_logical(), LogicalOutcome::Solved);
+    /// assert!(solvable.check_if_correct());
+    ///
+    /// let stalls: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let mut stalls = SudokuSolver::from_flat(&stalls).unwrap();
+    /// let LogicalOutcome::Stalled { board, candidates } = stalls.solve_logical() else { panic!("needs guessing") };
+    /// assert_eq!(board, stalls.as_array());
+    /// assert!(candidates.iter().flatten().all(|count| *count >= 1));
+    /// assert!(!stalls.is_solved());
+    /// ```
+    pub fn solve_logical(&mut self) -> LogicalOutcome {
+        self.solve_logical_with(TechniqueSet::all())
+    }
+
+    /// `solve_logical` with only `techniques`, so whether the puzzle gets solved tells if it takes nothing harder
+    /// than them, e.g. for grading a puzzle as solvable with beginner techniques or for hints at the level of a learner
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{LogicalOutcome, SudokuSolver, Technique, TechniqueSet};
+    ///
+    /// // this one needs a Swordfish, or a Jellyfish instead
+    /// let digits: Vec<u8> = "108000067000050000000000030006100040450000900000093000200040010003002700807001005"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    ///
+    /// let singles = Technique::NakedSingle | Technique::HiddenSingle;
+    /// assert!(matches!(puzzle.clone().solve_logical_with(singles), LogicalOutcome::Stalled { .. }));
+    /// let without = TechniqueSet::all().without(Technique::Swordfish).without(Technique::Jellyfish);
+    /// assert!(matches!(puzzle.clone().solve_logical_with(without), LogicalOutcome::Stalled { .. }));
+    /// assert_eq!(puzzle.clone().solve_logical_with(without | Technique::Swordfish), LogicalOutcome::Solved);
+    /// assert_eq!(puzzle.clone().solve_logical_with(without | Technique::Jellyfish), LogicalOutcome::Solved);
+    /// ```
+    pub fn solve_logical_with(&mut self, techniques: TechniqueSet) -> LogicalOutcome {
+        let config = SolveConfig { pipeline: techniques.pipeline(), ..SolveConfig::default() };
+        let mut report = SolveReport::default();
+        let outcome = loop {
+            if let Err(contradiction) = self.deduce(&config, &mut report) {
+                break LogicalOutcome::Unsolvable(contradiction);
+            }
+            match self.get_coords_of_uncollapsed_cell_with_lowest_entropy() {
+                None => break LogicalOutcome::Solved,
+                // a cell can only be left with one candidate without being collapsed on a board built from candidates
+                Some(cell) if self.get_cell(&cell).get_entropy() == 1 => {
+                    if let Err(contradiction) = self.collapse_cell_and_save_state(cell, None, &config, &mut report) {
+                        break LogicalOutcome::Unsolvable(contradiction);
+                    }
+                },
+                Some(_) => break LogicalOutcome::Stalled { board: self.as_array(), candidates: self.entropy_map() }
+            }
+        };
+        self.debug_view = self.to_string();
+        outcome
+    }
+
+    /// A cheap check for batch jobs to skip inputs that are clearly dead before solving them: false if placing the
+    /// naked and hidden singles of a copy of the board until there are none left runs into a contradiction.
+    /// True only means nothing went wrong that quickly, the puzzle can still turn out to have no solution.
+    /// Unlike `has_unique_solution` this never guesses, and every single it finds fills a cell, so it stays cheap
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let digits = |puzzle: &str| -> Vec<u8> { puzzle.bytes().map(|digit| digit - b'0').collect() };
+    /// let easy = SudokuSolver::from_flat(&digits("200000800005140003000000000030860007900400600000005100090370000076020000001009500")).unwrap();
+    /// assert!(easy.looks_solvable());
+    /// // the same puzzle with a wrong 1 in r1c2, which singles alone run into
+    /// let dead = SudokuSolver::from_flat(&digits("210000800005140003000000000030860007900400600000005100090370000076020000001009500")).unwrap();
+    /// assert!(!dead.looks_solvable());
+    ///
+    /// // a wrong 2 in r1c2 of a hard puzzle only shows once the search has run out of options
+    /// let mut hard = SudokuSolver::from_flat(&digits("820000000003600000070090200050007000000045700000100030001000068008500010090000400")).unwrap();
+    /// assert!(hard.looks_solvable());
+    /// assert!(hard.solve().is_err());
+    /// ```
+    pub fn looks_solvable(&self) -> bool {
+        let config = SolveConfig {
+            pipeline: TechniquePipeline::new([Technique::NakedSingle, Technique::HiddenSingle]),
+            ..SolveConfig::default()
+        };
+        let mut copy = self.search_copy();
+        copy.deduce(&config, &mut SolveReport::default()).is_ok() && copy.first_contradiction().is_none()
+    }
+
+    /// The empty cell whose value would help the most as a hint, for when a player is stuck: the one that lets
+    /// `solve_logical` fill the most cells once it is revealed, the first one in row-major order if several tie.
+    /// None if the board is already full or has no solution. Reveals the solution of every empty cell in turn,
+    /// so it costs a solve and then one `solve_logical` per empty cell. Nothing is changed. Only for 9x9 boards
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{LogicalOutcome, SudokuSolver};
+    ///
+    /// let digits: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let stuck = SudokuSolver::from_flat(&digits).unwrap();
+    /// let hint = stuck.best_clue_to_reveal().unwrap();
+    /// assert_eq!(stuck.as_array()[hint.y][hint.x], 0);
+    ///
+    /// let mut solution = stuck.clone();
+    /// solution.solve().unwrap();
+    /// let filled = |mut puzzle: Vec<u8>| {
+    ///     puzzle[hint.y * 9 + hint.x] = solution.as_array()[hint.y][hint.x];
+    ///     let mut puzzle = SudokuSolver::from_flat(&puzzle).unwrap();
+    ///     puzzle.solve_logical();
+    ///     puzzle.as_array().iter().flatten().filter(|digit| **digit != 0).count()
+    /// };
+    /// let mut without = stuck.clone();
+    /// assert!(matches!(without.solve_logical(), LogicalOutcome::Stalled { .. }));
+    /// assert!(filled(digits) > without.as_array().iter().flatten().filter(|digit| **digit != 0).count());
+    /// ```
+    pub fn best_clue_to_reveal(&self) -> Option<Point<usize>> {
+        let mut solution = self.search_copy();
+        solution.solve().ok()?;
+        let solution = solution.as_array();
+        let board = self.as_array();
+
+        let empty = (0..81).map(|i| Point::from_index(i, 9)).filter(|point| board[point.y][point.x] == 0);
+        let config = SolveConfig::default();
+        let mut best = None::<(Point<usize>, usize)>;
+        for point in empty {
+            let value = solution[point.y][point.x];
+            let mut revealed = self.search_copy();
+            revealed.board[point.y][point.x] = Cell::new_filled(value);
+            if revealed.propagate_and_collapse_forced(point, value, &config, &mut SolveReport::default()).is_err() {
+                continue;
+            }
+            let filled = match revealed.solve_logical() {
+                LogicalOutcome::Solved => 81,
+                LogicalOutcome::Stalled { board, .. } => board.iter().flatten().filter(|digit| **digit != 0).count(),
+                LogicalOutcome::Unsolvable(_) => continue
+            };
+            if best.is_none_or(|(_, most)| filled > most) {
+                best = Some((point, filled));
+            }
+        }
+        best.map(|(point, _)| point)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::point::Point;
+use super::{can_assign_distinct, ErrorSudokuContainsAContradiction, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::vec::Vec;
+
+/// Renban sudoku, the digits on a line are a set of consecutive digits in any order
+#[derive(Debug, Clone)]
+pub struct RenbanSudokuSolver {
+    solver: SudokuSolver,
+    lines: Vec<Vec<Point<usize>>>
+}
+
+impl RenbanSudokuSolver {
+    pub fn new(starting_state: [[u8; 9]; 9], lines: Vec<Vec<Point<usize>>>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Ok(RenbanSudokuSolver {
+            solver: SudokuSolver::new(starting_state)?,
+            lines
+        })
+    }
+
+    pub fn solver(&self) -> &SudokuSolver {
+        &self.solver
+    }
+
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        let lines = self.lines.clone();
+        self.solver.solve_with_strategy(&mut |solver| apply_renban_constraints(solver, &lines).map(|_| ()))
+    }
+
+    /// Removes candidates that are not part of any run of consecutive digits the line can still be filled with,
+    /// returns how many candidates were removed
+    pub fn apply_renban_constraints(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        apply_renban_constraints(&mut self.solver, &self.lines).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.solver.check_if_correct() && self.lines.iter().all(|line| {
+            let values: Option<Vec<u8>> = line.iter().map(|cell| self.solver.get_cell(cell).value()).collect();
+            let Some(mut values) = values else { return false };
+            values.sort();
+            values.windows(2).all(|pair| pair[0] + 1 == pair[1])
+        })
+    }
+}
+
+impl core::fmt::Display for RenbanSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.solver.fmt(f)
+    }
+}
+
+fn apply_renban_constraints(solver: &mut SudokuSolver, lines: &[Vec<Point<usize>>]) -> Result<usize, ()> {
+    let mut removed = 0;
+    for line in lines {
+        if line.is_empty() || line.len() > 9 {
+            return Err(());
+        }
+        let domains: Vec<u16> = line.iter().map(|cell| solver.get_candidate_mask(cell)).collect();
+        let mut allowed = 0u16;
+        // a run of line.len() digits starting at `lowest`, the digits of a line are all different so a run is also a set
+        for lowest in 1..=(10 - line.len()) {
+            let run = ((1u16 << line.len()) - 1) << lowest;
+            let restricted: Vec<u16> = domains.iter().map(|domain| domain & run).collect();
+            if can_assign_distinct(&restricted, 0) {
+                allowed |= run;
+            }
+        }
+
+        for (cell, domain) in line.iter().zip(domains) {
+            for value in 1..=9 {
+                if domain & !allowed & (1 << value) != 0 {
+                    solver.get_cell_mut(cell).remove(value)?;
+                    removed += 1;
+                }
+            }
+        }
+    }
+    Ok(removed)
+}
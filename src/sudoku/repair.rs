@@ -0,0 +1,58 @@
+use crate::point::Point;
+use super::SudokuSolver;
+
+use alloc::vec::Vec;
+
+impl SudokuSolver {
+    /// The smallest set of clues whose removal makes `starting_state` solvable, in row-major order.
+    /// Empty when the puzzle is already solvable. Nothing is mutated, the caller decides what to fix.
+    ///
+    /// Every pair of clues that directly conflict needs one of them removed, past that the clues
+    /// are tried in sets of growing size, so this is quick for a typo or two but gets exponentially
+    /// slower the more clues have to go
+    pub fn suggest_clue_removals(starting_state: [[u8; 9]; 9]) -> Vec<Point<usize>> {
+        let clues: Vec<Point<usize>> = (0..81).map(|i| Point::from_index(i, 9))
+            .filter(|point| starting_state[point.y][point.x] != 0)
+            .collect();
+        let value = |point: &Point<usize>| starting_state[point.y][point.x];
+        let conflicts: Vec<(Point<usize>, Point<usize>)> = clues.iter()
+            .flat_map(|clue| SudokuSolver::peers(*clue)
+                .filter(move |peer| peer.to_index(9) > clue.to_index(9) && value(peer) == value(clue))
+                .map(move |peer| (*clue, peer)))
+            .collect();
+
+        for removal_count in 0..=clues.len() {
+            let mut chosen: Vec<usize> = (0..removal_count).collect();
+            loop {
+                let removed: Vec<Point<usize>> = chosen.iter().map(|i| clues[*i]).collect();
+                let resolves_conflicts = conflicts.iter().all(|(a, b)| removed.contains(a) || removed.contains(b));
+                if resolves_conflicts && is_solvable_without(starting_state, &removed) {
+                    return removed;
+                }
+                if !next_combination(&mut chosen, clues.len()) {
+                    break;
+                }
+            }
+        }
+        unreachable!("an empty board is always solvable")
+    }
+}
+
+fn is_solvable_without(mut starting_state: [[u8; 9]; 9], removed: &[Point<usize>]) -> bool {
+    for point in removed {
+        starting_state[point.y][point.x] = 0;
+    }
+    SudokuSolver::new(starting_state).is_ok_and(|solver| solver.count_solutions(1) > 0)
+}
+
+/// Advances `chosen`, a sorted set of indices below `n`, to the next set of the same size in lexicographic order,
+/// returns false once every set was visited
+fn next_combination(chosen: &mut [usize], n: usize) -> bool {
+    let k = chosen.len();
+    let Some(i) = (0..k).rev().find(|i| chosen[*i] < n - k + i) else { return false };
+    chosen[i] += 1;
+    for j in i + 1..k {
+        chosen[j] = chosen[j - 1] + 1;
+    }
+    true
+}
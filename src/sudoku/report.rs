@@ -0,0 +1,251 @@
+use crate::point::Point;
+use super::{Contradiction, Deduction, SolveCancel, Technique, TechniquePipeline};
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// Limits and options for `SudokuSolver::solve_with_report`, the default has no limits, records no trace,
+/// collapses hidden singles and applies locked candidates, naked and hidden pairs and triples, X-Wings, XY-Wings, Swordfish and Jellyfish
+#[derive(Debug, Clone)]
+pub struct SolveConfig {
+    /// give up with `SudokuError::Timeout` once solving takes longer than this
+    #[cfg(feature = "std")]
+    pub timeout: Option<Duration>,
+    /// give up with `SudokuError::BacktrackLimitReached` instead of backtracking more times than this,
+    /// the board is left on the state the next backtrack returns to
+    pub max_backtracks: Option<usize>,
+    /// record every step in `SolveReport::trace`
+    pub trace: bool,
+    /// record the board after each guess in `SolveReport::snapshots`, only for 9x9 boards
+    pub snapshots: bool,
+    /// give up with `SudokuError::Cancelled` once this is cancelled
+    pub cancel: Option<SolveCancel>,
+    /// the techniques to apply before each guess and their order, every technique from the easiest to the hardest by default.
+    /// Only `SudokuSolver` and `WfcSolver` apply them, the other engines of `Solver` ignore them
+    pub pipeline: TechniquePipeline,
+    /// Shuffles the digits each guess tries and which of the cells with the fewest candidates it guesses on,
+    /// with this seed. The same seed always makes the same guesses, and without one guesses take the first such cell
+    /// in row-major order and the collapse order of the solver
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver};
+    ///
+    /// let complete = |seed| {
+    ///     let mut grid = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    ///     grid.solve_with_report(&SolveConfig { seed, ..SolveConfig::default() }).unwrap();
+    ///     assert!(grid.check_if_correct());
+    ///     grid.as_array()
+    /// };
+    /// assert_eq!(complete(Some(1)), complete(Some(1)));
+    /// assert_ne!(complete(Some(1)), complete(Some(2)));
+    /// assert_eq!(complete(None), complete(None));
+    /// ```
+    pub seed: Option<u64>,
+    /// the order each guess tries the digits of its cell in
+    pub value_order: ValueOrder
+}
+
+/// How a guess orders the digits it tries, see `SolveConfig::value_order`. It only changes how a solution is found
+///
+/// ```
+/// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, ValueOrder};
+///
+/// let digits: Vec<u8> = "800000000003600000070090200050007000000045700000100030001000068008500010090000400"
+///     .bytes().map(|digit| digit - b'0').collect();
+/// let mut collapse_order = SudokuSolver::from_flat(&digits).unwrap();
+/// let mut least_constraining = collapse_order.clone();
+/// collapse_order.solve().unwrap();
+/// let config = SolveConfig { value_order: ValueOrder::LeastConstraining, ..SolveConfig::default() };
+/// let report = least_constraining.solve_with_report(&config).unwrap();
+/// assert_eq!(report.value_order, ValueOrder::LeastConstraining);
+/// assert_eq!(least_constraining.as_array(), collapse_order.as_array());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ValueOrder {
+    /// the collapse order of the solver, or shuffled with `SolveConfig::seed`
+    #[default]
+    CollapseOrder,
+    /// Least constraining value: the digits that the fewest open peers of the cell can hold first, since they rule out
+    /// the fewest options and tend to lead to a solution sooner. Digits that tie keep the order of `CollapseOrder`
+    LeastConstraining
+}
+
+impl core::fmt::Display for ValueOrder {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValueOrder::CollapseOrder => write!(f, "collapse order"),
+            ValueOrder::LeastConstraining => write!(f, "least constraining value")
+        }
+    }
+}
+
+impl Default for SolveConfig {
+    fn default() -> Self {
+        SolveConfig {
+            #[cfg(feature = "std")]
+            timeout: None,
+            max_backtracks: None,
+            trace: false,
+            snapshots: false,
+            cancel: None,
+            pipeline: TechniquePipeline::default(),
+            seed: None,
+            value_order: ValueOrder::CollapseOrder
+        }
+    }
+}
+
+/// Statistics of a single solve
+#[derive(Debug, Clone, Default)]
+pub struct SolveReport {
+    /// always zero without the `std` feature, there is no clock to measure it with
+    pub elapsed: Duration,
+    /// iterations of the solving loop, each one either collapses a cell or backtracks
+    pub iterations: usize,
+    /// cells collapsed because they had only one candidate left or were the only place for a digit in a unit
+    pub forced_placements: usize,
+    /// the forced placements that were the only place for a digit in a unit
+    pub hidden_singles: usize,
+    /// naked pairs and triples that removed candidates, see `Deduction::NakedSubset`
+    pub naked_subsets: usize,
+    /// pointing and claiming that removed candidates, see `Deduction::Pointing` and `Deduction::Claiming`
+    pub locked_candidates: usize,
+    /// hidden pairs and triples that removed candidates, see `Deduction::HiddenSubset`
+    pub hidden_subsets: usize,
+    /// X-Wings that removed candidates, see `Deduction::Fish`
+    pub x_wings: usize,
+    /// XY-Wings that removed candidates, see `Deduction::XYWing`
+    pub xy_wings: usize,
+    /// Swordfish that removed candidates, see `Deduction::Fish`
+    pub swordfish: usize,
+    /// Jellyfish that removed candidates, see `Deduction::Fish`
+    pub jellyfish: usize,
+    /// the forced placements made while propagating another collapse rather than by the solving loop
+    pub cascaded_placements: usize,
+    /// cells collapsed to one of several candidates, each of these saves a state to backtrack to
+    pub guesses: usize,
+    pub backtracks: usize,
+    /// highest number of saved states at any point of the solve
+    pub max_depth: usize,
+    /// nodes of the search tree, every collapsed cell is one
+    pub nodes_visited: usize,
+    /// nodes where the search could not go any deeper, every contradiction and the solution
+    pub leaves_reached: usize,
+    /// candidates removed from peers by propagation, each one is a branch the search never has to explore
+    pub branches_pruned: usize,
+    /// how many cells had each number of candidates when they were collapsed, indexed by that number,
+    /// so index 1 counts the forced placements and everything above it counts guesses
+    pub branching: Vec<usize>,
+    /// empty unless `SolveConfig::trace` was set
+    pub trace: Vec<TraceEvent>,
+    /// the board right after each guess collapsed its cell, before the guess is propagated.
+    /// Empty unless `SolveConfig::snapshots` was set
+    pub snapshots: Vec<[[u8; 9]; 9]>,
+    /// `SolveConfig::value_order` of the solve
+    pub value_order: ValueOrder
+}
+
+/// A single step of the solve in the order it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent {
+    Collapse { cell: Point<usize>, value: u8, guess: bool },
+    /// a placement or elimination made without guessing, a placement is followed by its `Collapse`
+    Deduction(Deduction),
+    /// a contradiction was found and the solver returned to the last saved state, `depth` is the number of states left.
+    /// `contradiction` is None when the rules of a variant were broken rather than found by propagation
+    Backtrack { depth: usize, contradiction: Option<Contradiction> },
+    Solved
+}
+
+/// How many times each technique made a deduction during a solve and how many guesses were needed on top of them,
+/// see `SudokuSolver::solve_with_strategy_log`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StrategyLog {
+    /// forced placements of a cell's last candidate, including those made while propagating
+    pub naked_singles: usize,
+    pub hidden_singles: usize,
+    pub locked_candidates: usize,
+    /// naked pairs and triples
+    pub naked_subsets: usize,
+    /// hidden pairs and triples
+    pub hidden_subsets: usize,
+    pub x_wings: usize,
+    pub xy_wings: usize,
+    pub swordfish: usize,
+    pub jellyfish: usize,
+    pub guesses: usize
+}
+
+impl StrategyLog {
+    /// every technique with how many times it made a deduction, from the easiest to the hardest
+    pub fn by_technique(&self) -> [(Technique, usize); 9] {
+        [
+            (Technique::NakedSingle, self.naked_singles),
+            (Technique::HiddenSingle, self.hidden_singles),
+            (Technique::LockedCandidates, self.locked_candidates),
+            (Technique::NakedSubset, self.naked_subsets),
+            (Technique::HiddenSubset, self.hidden_subsets),
+            (Technique::XWing, self.x_wings),
+            (Technique::XYWing, self.xy_wings),
+            (Technique::Swordfish, self.swordfish),
+            (Technique::Jellyfish, self.jellyfish)
+        ]
+    }
+}
+
+impl From<&SolveReport> for StrategyLog {
+    fn from(report: &SolveReport) -> Self {
+        StrategyLog {
+            naked_singles: report.forced_placements.saturating_sub(report.hidden_singles),
+            hidden_singles: report.hidden_singles,
+            locked_candidates: report.locked_candidates,
+            naked_subsets: report.naked_subsets,
+            hidden_subsets: report.hidden_subsets,
+            x_wings: report.x_wings,
+            xy_wings: report.xy_wings,
+            swordfish: report.swordfish,
+            jellyfish: report.jellyfish,
+            guesses: report.guesses
+        }
+    }
+}
+
+impl SolveReport {
+    /// The hardest technique the solve needed, a rough measure of how hard the puzzle is for a human.
+    /// None if nothing was deduced, which means every placement was a guess
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        StrategyLog::from(self).by_technique().into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(technique, _)| technique)
+            .max()
+    }
+}
+
+impl core::fmt::Display for SolveReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "Time: {:?}", self.elapsed)?;
+        writeln!(f, "Iterations: {}", self.iterations)?;
+        writeln!(f, "Forced placements: {}", self.forced_placements)?;
+        writeln!(f, "Cascaded placements: {}", self.cascaded_placements)?;
+        writeln!(f, "Hidden singles: {}", self.hidden_singles)?;
+        writeln!(f, "Locked candidates: {}", self.locked_candidates)?;
+        writeln!(f, "Naked subsets: {}", self.naked_subsets)?;
+        writeln!(f, "Hidden subsets: {}", self.hidden_subsets)?;
+        writeln!(f, "X-Wings: {}", self.x_wings)?;
+        writeln!(f, "XY-Wings: {}", self.xy_wings)?;
+        writeln!(f, "Swordfish: {}", self.swordfish)?;
+        writeln!(f, "Jellyfish: {}", self.jellyfish)?;
+        match self.hardest_technique() {
+            Some(technique) => writeln!(f, "Hardest technique: {}", technique)?,
+            None => writeln!(f, "Hardest technique: none")?
+        }
+        writeln!(f, "Guesses: {}", self.guesses)?;
+        writeln!(f, "Value order: {}", self.value_order)?;
+        writeln!(f, "Backtracks: {}", self.backtracks)?;
+        writeln!(f, "Max depth: {}", self.max_depth)?;
+        writeln!(f, "Nodes visited: {}", self.nodes_visited)?;
+        writeln!(f, "Leaves reached: {}", self.leaves_reached)?;
+        writeln!(f, "Branches pruned: {}", self.branches_pruned)?;
+        write!(f, "Branching profile: {:?}", self.branching)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::cell::Cell;
+use crate::point::Point;
+use super::{ErrorSudokuContainsAContradiction, Sudoku, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::{string::String, vec::Vec};
+
+/// Samurai sudoku, five 9x9 grids on a 21x21 board where each corner grid shares its inner region with the center grid
+#[derive(Debug, Clone)]
+pub struct SamuraiSudokuSolver {
+    /// top left, top right, center, bottom left and bottom right
+    grids: [SudokuSolver; 5],
+    /// the solving stack of the whole puzzle, the grids never use their own
+    previous_states: Vec<[Sudoku; 5]>,
+    /// every cell that is part of two grids
+    shared_cells: Vec<SharedCell>
+}
+
+/// a cell that is part of two grids, as (grid, cell in that grid) for both grids
+type SharedCell = ((usize, Point<usize>), (usize, Point<usize>));
+
+/// top left corner of every grid on the 21x21 board
+const GRID_OFFSETS: [(usize, usize); 5] = [(0, 0), (12, 0), (6, 6), (0, 12), (12, 12)];
+
+impl SamuraiSudokuSolver {
+    /// `starting_states` are the grids in the order top left, top right, center, bottom left and bottom right,
+    /// a shared cell can be given in either grid or in both of them
+    pub fn new(starting_states: [[[u8; 9]; 9]; 5]) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        let grids = [
+            SudokuSolver::new(starting_states[0])?,
+            SudokuSolver::new(starting_states[1])?,
+            SudokuSolver::new(starting_states[2])?,
+            SudokuSolver::new(starting_states[3])?,
+            SudokuSolver::new(starting_states[4])?
+        ];
+        let mut samurai = SamuraiSudokuSolver {
+            grids,
+            previous_states: Vec::new(),
+            shared_cells: shared_cells()
+        };
+        samurai.sync_shared_cells().map_err(|_| ErrorSudokuContainsAContradiction::default())?;
+        Ok(samurai)
+    }
+
+    /// in the order top left, top right, center, bottom left and bottom right
+    pub fn grids(&self) -> &[SudokuSolver; 5] {
+        &self.grids
+    }
+
+    /// Solves all five grids together, a collapse in a shared cell is copied into the other grid right away
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        loop {
+            match self.sync_shared_cells().and_then(|_| self.solve_iteration()) {
+                Ok(true) => return Ok(()),
+                Ok(false) => continue,
+                Err(_) => match self.previous_states.pop() {
+                    Some(previous_state) => {
+                        for (grid, board) in self.grids.iter_mut().zip(previous_state) {
+                            grid.board = board;
+                        }
+                    },
+                    None => return Err(SudokuIsUnsolvable)
+                }
+            }
+        }
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.grids.iter().all(|grid| grid.check_if_correct())
+            && self.shared_cells.iter().all(|((a, a_point), (b, b_point))| {
+                self.grids[*a].get_cell(a_point).value() == self.grids[*b].get_cell(b_point).value()
+            })
+    }
+
+    /// collapses the cell with the lowest entropy out of all grids, returns true when everything is solved
+    fn solve_iteration(&mut self) -> Result<bool, ()> {
+        let lowest = self.grids.iter().enumerate()
+            .filter_map(|(i, grid)| grid.get_coords_of_uncollapsed_cell_with_lowest_entropy().map(|point| (i, point)))
+            .min_by_key(|(i, point)| self.grids[*i].get_cell(point).get_entropy());
+        let Some((grid, point)) = lowest else { return Ok(true) };
+
+        let solver = &mut self.grids[grid];
+        let cell = &mut solver.board[point.y][point.x];
+        let should_save = cell.get_entropy() > 1;
+        let value_with_collapsed_num_removed = cell.collapse(&solver.collapse_order);
+        let Cell::Collapsed(collapsed_to_num) = *cell else { unreachable!() };
+
+        if should_save {
+            let mut boards: [Sudoku; 5] = core::array::from_fn(|i| self.grids[i].board.clone());
+            boards[grid][point.y][point.x] = value_with_collapsed_num_removed;
+            self.previous_states.push(boards);
+        }
+
+        self.grids[grid].propagate_collapse(point, collapsed_to_num).map_err(|_| ())?;
+        Ok(false)
+    }
+
+    /// Copies collapsed shared cells into the other grid and narrows both copies of every
+    /// uncollapsed shared cell down to the candidates they have in common
+    fn sync_shared_cells(&mut self) -> Result<(), ()> {
+        for i in 0..self.shared_cells.len() {
+            let ((a, a_point), (b, b_point)) = self.shared_cells[i];
+            match (self.grids[a].get_cell(&a_point).value(), self.grids[b].get_cell(&b_point).value()) {
+                (Some(a_value), Some(b_value)) if a_value != b_value => return Err(()),
+                (Some(_), Some(_)) => continue,
+                (Some(value), None) => self.collapse_shared(b, b_point, value)?,
+                (None, Some(value)) => self.collapse_shared(a, a_point, value)?,
+                (None, None) => {
+                    let a_candidates = self.grids[a].get_candidates(&a_point);
+                    let b_candidates = self.grids[b].get_candidates(&b_point);
+                    for value in a_candidates.difference(&b_candidates) {
+                        self.grids[a].get_cell_mut(&a_point).remove(*value)?;
+                    }
+                    for value in b_candidates.difference(&a_candidates) {
+                        self.grids[b].get_cell_mut(&b_point).remove(*value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn collapse_shared(&mut self, grid: usize, point: Point<usize>, value: u8) -> Result<(), ()> {
+        if !self.grids[grid].get_cell(&point).contains(value) {
+            return Err(());
+        }
+        *self.grids[grid].get_cell_mut(&point) = Cell::new_filled(value);
+        self.grids[grid].propagate_collapse(point, value).map_err(|_| ())
+    }
+
+    /// the grid and the cell within it for a point of the 21x21 board, the first grid wins for shared cells
+    fn grid_cell(&self, point: Point<usize>) -> Option<(usize, Point<usize>)> {
+        grids_containing(point).next()
+    }
+}
+
+fn grids_containing(point: Point<usize>) -> impl Iterator<Item = (usize, Point<usize>)> {
+    GRID_OFFSETS.into_iter().enumerate().filter_map(move |(i, offset)| {
+        point.checked_sub(Point::from(offset))
+            .filter(|local| local.x < 9 && local.y < 9)
+            .map(|local| (i, local))
+    })
+}
+
+fn shared_cells() -> Vec<SharedCell> {
+    let mut shared = Vec::new();
+    for y in 0..21 {
+        for x in 0..21 {
+            let mut grids = grids_containing(Point::new(x, y));
+            if let (Some(first), Some(second)) = (grids.next(), grids.next()) {
+                shared.push((first, second));
+            }
+        }
+    }
+    shared
+}
+
+/// the 21x21 board, empty cells are `.` and the parts of the board that belong to no grid are blank
+impl core::fmt::Display for SamuraiSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for y in 0..21 {
+            let mut line = String::with_capacity(42);
+            for x in 0..21 {
+                let c = match self.grid_cell(Point::new(x, y)) {
+                    Some((grid, point)) => self.grids[grid].get_cell(&point).value()
+                        .map_or('.', |value| char::from(b'0' + value)),
+                    None => ' '
+                };
+                line.push(c);
+                line.push(' ');
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::point::Point;
+use super::{can_assign_distinct, ErrorSudokuContainsAContradiction, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::{vec, vec::Vec};
+
+/// Sandwich sudoku, a clue next to a row or column is the sum of the digits between the 1 and the 9 of that line
+#[derive(Debug, Clone)]
+pub struct SandwichSudokuSolver {
+    solver: SudokuSolver,
+    row_sums: [Option<u8>; 9],
+    col_sums: [Option<u8>; 9]
+}
+
+impl SandwichSudokuSolver {
+    pub fn new(starting_state: [[u8; 9]; 9], row_sums: [Option<u8>; 9], col_sums: [Option<u8>; 9]) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Ok(SandwichSudokuSolver {
+            solver: SudokuSolver::new(starting_state)?,
+            row_sums,
+            col_sums
+        })
+    }
+
+    pub fn solver(&self) -> &SudokuSolver {
+        &self.solver
+    }
+
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        let (row_sums, col_sums) = (self.row_sums, self.col_sums);
+        self.solver.solve_with_strategy(&mut |solver| apply_sandwich_constraints(solver, &row_sums, &col_sums).map(|_| ()))
+    }
+
+    /// Removes every candidate that cannot be part of any filling of a line matching its sum,
+    /// returns how many candidates were removed
+    pub fn apply_sandwich_constraints(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        apply_sandwich_constraints(&mut self.solver, &self.row_sums, &self.col_sums).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.solver.check_if_correct() && (0..9).all(|i| {
+            line_sum_matches(&self.solver, &row(i), self.row_sums[i]) && line_sum_matches(&self.solver, &column(i), self.col_sums[i])
+        })
+    }
+}
+
+impl core::fmt::Display for SandwichSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.solver.fmt(f)
+    }
+}
+
+fn row(y: usize) -> Vec<Point<usize>> {
+    (0..9).map(|x| Point::new(x, y)).collect()
+}
+
+fn column(x: usize) -> Vec<Point<usize>> {
+    (0..9).map(|y| Point::new(x, y)).collect()
+}
+
+fn line_sum_matches(solver: &SudokuSolver, line: &[Point<usize>], sum: Option<u8>) -> bool {
+    let Some(sum) = sum else { return true };
+    let values: Vec<u8> = line.iter().map(|point| solver.get_candidates(point).into_iter().next().unwrap_or(0)).collect();
+    let (Some(one), Some(nine)) = (values.iter().position(|v| *v == 1), values.iter().position(|v| *v == 9)) else { return false };
+    let between = one.min(nine) + 1..one.max(nine);
+    values[between].iter().map(|v| *v as u32).sum::<u32>() == sum as u32
+}
+
+fn apply_sandwich_constraints(solver: &mut SudokuSolver, row_sums: &[Option<u8>; 9], col_sums: &[Option<u8>; 9]) -> Result<usize, ()> {
+    let mut removed = 0;
+    for i in 0..9 {
+        if let Some(sum) = row_sums[i] {
+            removed += restrict_line(solver, &row(i), sum)?;
+        }
+        if let Some(sum) = col_sums[i] {
+            removed += restrict_line(solver, &column(i), sum)?;
+        }
+    }
+    Ok(removed)
+}
+
+/// Tries every position of the 1 and the 9 and every set of digits that could go between them,
+/// each cell keeps only the candidates used by at least one of the arrangements that still fit.
+/// Digit sets are bitmasks (bit `d` set means digit `d` is in the set) since this runs before every iteration
+fn restrict_line(solver: &mut SudokuSolver, line: &[Point<usize>], sum: u8) -> Result<usize, ()> {
+    let domains: Vec<u16> = line.iter().map(|point| solver.get_candidate_mask(point)).collect();
+    let mut allowed = vec![0u16; line.len()];
+
+    for one in 0..line.len() {
+        for nine in 0..line.len() {
+            if one == nine || domains[one] & (1 << 1) == 0 || domains[nine] & (1 << 9) == 0 {
+                continue;
+            }
+            let between = one.min(nine) + 1..one.max(nine);
+            for middle_digits in digit_sets_with_sum(between.len(), sum) {
+                let outer_digits = MIDDLE_DIGITS & !middle_digits;
+                let restricted: Vec<u16> = (0..line.len()).map(|i| {
+                    if i == one || i == nine {
+                        0
+                    } else if between.contains(&i) {
+                        domains[i] & middle_digits
+                    } else {
+                        domains[i] & outer_digits
+                    }
+                }).collect();
+                let middle: Vec<u16> = between.clone().map(|i| restricted[i]).collect();
+                let outer: Vec<u16> = (0..line.len()).filter(|i| *i != one && *i != nine && !between.contains(i))
+                    .map(|i| restricted[i]).collect();
+                if !can_assign_distinct(&middle, 0) || !can_assign_distinct(&outer, 0) {
+                    continue;
+                }
+                allowed[one] |= 1 << 1;
+                allowed[nine] |= 1 << 9;
+                for (i, digits) in restricted.into_iter().enumerate() {
+                    allowed[i] |= digits;
+                }
+            }
+        }
+    }
+
+    let mut removed = 0;
+    for (i, point) in line.iter().enumerate() {
+        for value in 1..=9 {
+            if domains[i] & !allowed[i] & (1 << value) != 0 {
+                solver.get_cell_mut(point).remove(value)?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// digits 2 to 8, the ones that can be between the 1 and the 9
+const MIDDLE_DIGITS: u16 = 0b1_1111_1100;
+
+/// every set of `size` distinct digits from 2 to 8 adding up to `sum`
+fn digit_sets_with_sum(size: usize, sum: u8) -> impl Iterator<Item = u16> {
+    (0u16..1 << 7)
+        .map(|mask| mask << 2)
+        .filter(move |mask| mask.count_ones() as usize == size)
+        .filter(move |mask| (2..=8).filter(|digit| mask & (1 << digit) != 0).sum::<u32>() == sum as u32)
+}
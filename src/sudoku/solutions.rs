@@ -0,0 +1,203 @@
+use crate::cell::Cell;
+use crate::point::Point;
+use super::{SolveConfig, SudokuError, SudokuSolver};
+
+use alloc::{string::{String, ToString}, vec::Vec};
+
+/// Which solution `SudokuSolver::solve_with_mode` settles on when a board has more than one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SolveMode {
+    /// whichever solution is found first, like `solve`
+    #[default]
+    Any,
+    /// fail with `SudokuError::NotUnique` if there is a second solution
+    RequireUnique,
+    /// the lexicographically smallest solution, comparing the cells in row-major order
+    Canonical
+}
+
+/// Iterator over every solution of a sudoku, see `SudokuSolver::solutions`
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    solver: SudokuSolver,
+    /// the solver is sitting on a solution that was already returned
+    on_solution: bool,
+    exhausted: bool
+}
+
+impl SudokuSolver {
+    /// Lazily finds every solution, each one exactly once and always in the same order.
+    /// After a solution is returned the search backtracks from it and continues, so nothing is precomputed
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let solutions = |puzzle: &str| {
+    ///     let digits: Vec<u8> = puzzle.bytes().map(|digit| digit - b'0').collect();
+    ///     SudokuSolver::from_flat(&digits).unwrap().solutions().collect::<Vec<_>>()
+    /// };
+    ///
+    /// // a wrong 2 in r1c2 that only the search runs into
+    /// assert!(solutions("820000000003600000070090200050007000000045700000100030001000068008500010090000400").is_empty());
+    ///
+    /// let unique = solutions("200000800005140003000000000030860007900400600000005100090370000076020000001009500");
+    /// assert_eq!(unique.len(), 1);
+    /// assert_eq!(unique[0][0], [2, 6, 3, 9, 5, 7, 8, 4, 1]);
+    ///
+    /// // the same solution without r1c1, r1c2, r6c1 and r6c2, where 2 and 6 can be swapped
+    /// let two = solutions("003957841785146293419238765134862957957413628008795134592371486876524319341689572");
+    /// assert_eq!(two.len(), 2);
+    /// assert_ne!(two[0], two[1]);
+    /// assert!(two.contains(&unique[0]));
+    /// assert_eq!(two, solutions("003957841785146293419238765134862957957413628008795134592371486876524319341689572"));
+    /// ```
+    pub fn solutions(self) -> Solutions {
+        Solutions {
+            solver: self,
+            on_solution: false,
+            exhausted: false
+        }
+    }
+
+    /// Counts solutions of the current board, stopping as soon as `limit` of them are found.
+    /// Works on a copy of the board without the saved states, so `self` is left untouched.
+    /// `with_max_backtrack_depth` is ignored, a search stopped by it would miss solutions
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let limited = SudokuSolver::new([[0; 9]; 9]).unwrap().with_max_backtrack_depth(0);
+    /// assert_eq!(limited.count_solutions(2), 2);
+    /// assert!(!limited.has_unique_solution());
+    /// ```
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut copy = self.search_copy();
+        copy.max_backtrack_depth = None;
+        copy.solutions().take(limit).count()
+    }
+
+    /// Up to `n` distinct solutions in the order `solutions` finds them, fewer when the board has fewer than `n`.
+    /// The board is left on the first of them like after `solve`, or untouched when there are none
+    pub fn solve_n(&mut self, n: usize) -> Vec<[[u8; 9]; 9]> {
+        let mut solutions = self.search_copy().solutions();
+        let mut found = Vec::with_capacity(n);
+        let mut first_board = None;
+        while found.len() < n {
+            let Some(solution) = solutions.next() else { break };
+            if first_board.is_none() {
+                first_board = Some(solutions.solver.board.clone());
+            }
+            found.push(solution);
+        }
+        if let Some(board) = first_board {
+            self.board = board;
+            self.previous_states.clear();
+            self.debug_view = self.to_string();
+        }
+        found
+    }
+
+    /// Solves like `solve` but decides what happens when the board has several solutions, see `SolveMode`.
+    /// On an error the board is left untouched.
+    /// `RequireUnique` costs about as much as `has_unique_solution` and `Canonical` checks that a solution is left
+    /// for each candidate it tries, which takes up to one solve per candidate on the board
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveMode, SudokuError, SudokuSolver};
+    ///
+    /// let mut empty = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    /// assert_eq!(empty.solve_with_mode(SolveMode::RequireUnique), Err(SudokuError::NotUnique));
+    /// empty.solve_with_mode(SolveMode::Canonical).unwrap();
+    /// assert_eq!(empty.as_array()[0], [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// assert_eq!(empty.as_array()[1], [4, 5, 6, 7, 8, 9, 1, 2, 3]);
+    /// ```
+    pub fn solve_with_mode(&mut self, mode: SolveMode) -> Result<(), SudokuError> {
+        let solved = match mode {
+            SolveMode::Any => return self.solve().map_err(SudokuError::from),
+            SolveMode::RequireUnique => {
+                let mut solutions = self.search_copy().solutions();
+                solutions.next().ok_or(SudokuError::Unsolvable)?;
+                let solved = solutions.solver.board.clone();
+                if solutions.next().is_some() {
+                    return Err(SudokuError::NotUnique);
+                }
+                solved
+            },
+            SolveMode::Canonical => self.canonical_solution().ok_or(SudokuError::Unsolvable)?
+        };
+        self.board = solved;
+        self.previous_states.clear();
+        self.debug_view = self.to_string();
+        Ok(())
+    }
+
+    /// the lexicographically smallest solution, found by giving every empty cell in row-major order
+    /// the smallest candidate that still leaves a solution
+    fn canonical_solution(&self) -> Option<super::Sudoku> {
+        let size = self.size();
+        let mut board = self.search_copy();
+        for point in (0..size * size).map(|i| Point::from_index(i, size)) {
+            if board.get_cell(&point).is_collapsed() {
+                continue;
+            }
+            board = board.get_candidates(&point).into_iter().find_map(|value| {
+                let mut placed = board.search_copy();
+                *placed.get_cell_mut(&point) = Cell::new_filled(value);
+                placed.propagate_collapse(point, value).ok()?;
+                (placed.count_solutions(1) == 1).then_some(placed)
+            })?;
+        }
+        Some(board.board)
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// A solver with the same board but nothing to backtrack to, the saved states belong to the search
+    /// that led to the current board and searching past them would visit boards that are not its descendants
+    pub(super) fn search_copy(&self) -> SudokuSolver {
+        SudokuSolver {
+            board: self.board.clone(),
+            previous_states: Vec::new(),
+            debug_view: String::new(),
+            extra_regions: self.extra_regions.clone(),
+            box_width: self.box_width,
+            box_height: self.box_height,
+            peers: self.peers.clone(),
+            givens: self.givens.clone(),
+            collapse_order: self.collapse_order.clone(),
+            max_backtrack_depth: self.max_backtrack_depth
+        }
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = [[u8; 9]; 9];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if self.on_solution {
+            match self.solver.previous_states.pop() {
+                Some(previous_state) => self.solver.board = previous_state,
+                None => {
+                    self.exhausted = true;
+                    return None;
+                }
+            }
+        }
+
+        match self.solver.run_search(&SolveConfig::default(), &mut |_| Ok(())) {
+            Ok(_) => {
+                self.on_solution = true;
+                Some(self.solver.as_array())
+            },
+            Err(_) => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::{SolveConfig, SolveReport, SudokuSolver};
+
+/// Iterator over the boards of a solve one iteration of the solving loop at a time, see `SudokuSolver::steps`
+#[derive(Debug, Clone)]
+pub struct SolveSteps {
+    solver: SudokuSolver,
+    report: SolveReport,
+    solved: bool,
+    finished: bool
+}
+
+impl SudokuSolver {
+    /// Solves lazily, every `next` runs one iteration of the solving loop and yields the board after it,
+    /// e.g. to draw one frame per step. An iteration collapses a cell along with everything that forces,
+    /// or backtracks to the last saved state after a contradiction.
+    ///
+    /// When the board has a solution the last item is that solution and `SolveSteps::is_solved` turns true.
+    /// When it has none the last item is the board before the contradiction nothing could be backtracked from,
+    /// and `is_solved` stays false. Either way every `next` after that returns None. Only for 9x9 boards
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut solution = SudokuSolver::new([[0; 9]; 9]).unwrap();
+    /// solution.solve().unwrap();
+    ///
+    /// let mut steps = SudokuSolver::new([[0; 9]; 9]).unwrap().steps();
+    /// let frames: Vec<[[u8; 9]; 9]> = steps.by_ref().collect();
+    /// assert!(steps.is_solved());
+    /// assert_eq!(frames.last(), Some(&solution.as_array()));
+    /// assert_eq!(steps.next(), None);
+    /// ```
+    pub fn steps(self) -> SolveSteps {
+        SolveSteps {
+            solver: self,
+            report: SolveReport::default(),
+            solved: false,
+            finished: false
+        }
+    }
+}
+
+impl SolveSteps {
+    /// true once the solution has been yielded
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Statistics of the iterations run so far
+    pub fn report(&self) -> &SolveReport {
+        &self.report
+    }
+}
+
+impl Iterator for SolveSteps {
+    type Item = [[u8; 9]; 9];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.solver.search_step(&SolveConfig::default(), &mut |_| Ok(()), &mut self.report) {
+            Ok(solved) => {
+                self.solved = solved;
+                self.finished = solved;
+                Some(self.solver.as_array())
+            },
+            Err(_) => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,1052 @@
+use crate::cell::Cell;
+use crate::point::Point;
+use super::{Contradiction, SolveConfig, SolveReport, SudokuIsUnsolvable, SudokuSolver, TraceEvent};
+
+use alloc::{collections::BTreeSet, format, vec, vec::Vec};
+use itertools::Itertools;
+
+/// Logical techniques the solver uses instead of guessing, ordered from the easiest to the hardest.
+/// Only the singles force a value into a cell, the others remove candidates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Technique {
+    /// the cell has only one candidate left
+    NakedSingle,
+    /// the digit has only one possible cell left in a row, column or region
+    HiddenSingle,
+    /// pointing or claiming, see `Deduction::Pointing` and `Deduction::Claiming`
+    LockedCandidates,
+    /// see `Deduction::NakedSubset`
+    NakedSubset,
+    /// see `Deduction::HiddenSubset`
+    HiddenSubset,
+    /// a fish on two lines, see `Deduction::Fish`
+    XWing,
+    /// see `Deduction::XYWing`
+    XYWing,
+    /// a fish on three lines, see `Deduction::Fish`
+    Swordfish,
+    /// a fish on four lines, see `Deduction::Fish`
+    Jellyfish
+}
+
+impl core::fmt::Display for Technique {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Technique::NakedSingle => write!(f, "naked single"),
+            Technique::HiddenSingle => write!(f, "hidden single"),
+            Technique::LockedCandidates => write!(f, "locked candidates"),
+            Technique::NakedSubset => write!(f, "naked subset"),
+            Technique::HiddenSubset => write!(f, "hidden subset"),
+            Technique::XWing => write!(f, "X-Wing"),
+            Technique::XYWing => write!(f, "XY-Wing"),
+            Technique::Swordfish => write!(f, "Swordfish"),
+            Technique::Jellyfish => write!(f, "Jellyfish")
+        }
+    }
+}
+
+impl Technique {
+    /// every technique from the easiest to the hardest
+    pub const ALL: [Technique; 9] = [
+        Technique::NakedSingle, Technique::HiddenSingle, Technique::LockedCandidates, Technique::NakedSubset,
+        Technique::HiddenSubset, Technique::XWing, Technique::XYWing, Technique::Swordfish, Technique::Jellyfish
+    ];
+
+    /// the name `--techniques` and `--disable-technique` select it by
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "singles",
+            Technique::HiddenSingle => "hidden-singles",
+            Technique::LockedCandidates => "locked",
+            Technique::NakedSubset => "naked-subsets",
+            Technique::HiddenSubset => "hidden-subsets",
+            Technique::XWing => "xwing",
+            Technique::XYWing => "xywing",
+            Technique::Swordfish => "swordfish",
+            Technique::Jellyfish => "jellyfish"
+        }
+    }
+
+    /// the technique with `name`, see `name`
+    pub fn by_name(name: &str) -> Option<Technique> {
+        Technique::ALL.into_iter().find(|technique| technique.name() == name)
+    }
+}
+
+/// The techniques a solve applies before each guess, in the order it tries them. After any of them makes progress
+/// the next deduction is looked for from the top again, so the cheap techniques go first.
+/// Hidden singles are always exhausted before the rest, and naked singles cannot be turned off since propagation
+/// collapses them as it goes, so their place in the order does not matter.
+/// See `SolveConfig::pipeline`, the default holds every technique from the easiest to the hardest
+///
+/// Turning a technique off only changes how the solution is found:
+/// ```
+/// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, Technique, TechniquePipeline};
+///
+/// let digits: Vec<u8> = "108000067000050000000000030006100040450000900000093000200040010003002700807001005"
+///     .bytes().map(|digit| digit - b'0').collect();
+/// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+///
+/// let mut all = puzzle.clone();
+/// let report = all.solve_with_report(&SolveConfig::default()).unwrap();
+/// assert_eq!(report.guesses, 0);
+///
+/// // a Jellyfish would do instead of the Swordfish
+/// let pipeline = TechniquePipeline::default().without(Technique::Swordfish).without(Technique::Jellyfish);
+/// let mut without = puzzle.clone();
+/// let without_report = without.solve_with_report(&SolveConfig { pipeline, ..SolveConfig::default() }).unwrap();
+/// assert!(without_report.guesses > 0);
+/// assert_eq!(without_report.swordfish, 0);
+/// assert_eq!(without.as_array(), all.as_array());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TechniquePipeline(Vec<Technique>);
+
+impl TechniquePipeline {
+    /// applies `techniques` in the order they are given, a technique listed twice is only kept the first time
+    pub fn new(techniques: impl IntoIterator<Item = Technique>) -> Self {
+        let mut pipeline = Vec::new();
+        for technique in techniques {
+            if !pipeline.contains(&technique) {
+                pipeline.push(technique);
+            }
+        }
+        TechniquePipeline(pipeline)
+    }
+
+    /// the same pipeline without `technique`
+    pub fn without(mut self, technique: Technique) -> Self {
+        self.0.retain(|kept| *kept != technique);
+        self
+    }
+
+    pub fn contains(&self, technique: Technique) -> bool {
+        self.0.contains(&technique)
+    }
+
+    pub fn techniques(&self) -> &[Technique] {
+        &self.0
+    }
+}
+
+impl Default for TechniquePipeline {
+    fn default() -> Self {
+        TechniquePipeline(Technique::ALL.to_vec())
+    }
+}
+
+/// Which techniques are allowed, as one bit per technique, for `SudokuSolver::solve_logical_with`.
+/// Sets combine with `|`, e.g. `Technique::NakedSingle | Technique::HiddenSingle` for singles only,
+/// and unlike a `TechniquePipeline` they have no order, the techniques are always tried from the easiest up.
+/// Naked singles are collapsed by propagation either way, so leaving them out changes nothing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TechniqueSet(u16);
+
+impl TechniqueSet {
+    /// no technique at all, which leaves only the naked singles of propagation
+    pub const fn empty() -> Self {
+        TechniqueSet(0)
+    }
+
+    /// every technique
+    pub const fn all() -> Self {
+        TechniqueSet((1 << Technique::ALL.len()) - 1)
+    }
+
+    pub const fn contains(&self, technique: Technique) -> bool {
+        self.0 & TechniqueSet::bit(technique) != 0
+    }
+
+    /// the same set with `technique`
+    pub const fn with(self, technique: Technique) -> Self {
+        TechniqueSet(self.0 | TechniqueSet::bit(technique))
+    }
+
+    /// the same set without `technique`
+    pub const fn without(self, technique: Technique) -> Self {
+        TechniqueSet(self.0 & !TechniqueSet::bit(technique))
+    }
+
+    /// the techniques of the set from the easiest to the hardest
+    pub fn iter(&self) -> impl Iterator<Item = Technique> + '_ {
+        Technique::ALL.into_iter().filter(|technique| self.contains(*technique))
+    }
+
+    /// the techniques of the set as a pipeline from the easiest to the hardest
+    pub fn pipeline(&self) -> TechniquePipeline {
+        TechniquePipeline::new(self.iter())
+    }
+
+    const fn bit(technique: Technique) -> u16 {
+        1 << technique as u16
+    }
+}
+
+impl From<Technique> for TechniqueSet {
+    fn from(technique: Technique) -> Self {
+        TechniqueSet::empty().with(technique)
+    }
+}
+
+impl FromIterator<Technique> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = Technique>>(techniques: I) -> Self {
+        techniques.into_iter().fold(TechniqueSet::empty(), TechniqueSet::with)
+    }
+}
+
+impl core::ops::BitOr for TechniqueSet {
+    type Output = TechniqueSet;
+
+    fn bitor(self, other: TechniqueSet) -> TechniqueSet {
+        TechniqueSet(self.0 | other.0)
+    }
+}
+
+impl core::ops::BitOr<Technique> for TechniqueSet {
+    type Output = TechniqueSet;
+
+    fn bitor(self, technique: Technique) -> TechniqueSet {
+        self.with(technique)
+    }
+}
+
+impl core::ops::BitOr for Technique {
+    type Output = TechniqueSet;
+
+    fn bitor(self, other: Technique) -> TechniqueSet {
+        TechniqueSet::from(self).with(other)
+    }
+}
+
+/// A row, column or region, all of which have to contain every digit once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// the row with this y coordinate
+    Row(usize),
+    /// the column with this x coordinate
+    Column(usize),
+    /// the region with this `SudokuSolver::region_index`
+    Region(usize),
+    /// an extra region of a variant such as windoku, in the order the variant added them
+    Extra(usize)
+}
+
+impl core::fmt::Display for Unit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Unit::Row(y) => write!(f, "row {}", y + 1),
+            Unit::Column(x) => write!(f, "column {}", x + 1),
+            Unit::Region(i) => write!(f, "region {}", i + 1),
+            Unit::Extra(i) => write!(f, "extra region {}", i + 1)
+        }
+    }
+}
+
+/// Why solving placed or eliminated a digit without guessing, recorded in `SolveReport::trace` for explaining a solve
+///
+/// ```
+/// use sudoku_solver_cli::sudoku::{Deduction, SolveConfig, SudokuSolver, Technique, TechniquePipeline, TraceEvent};
+///
+/// let digits: Vec<u8> = "200000800005140003000000000030860007900400600000005100090370000076020000001009500"
+///     .bytes().map(|digit| digit - b'0').collect();
+/// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+/// let report = puzzle.clone().solve_with_report(&SolveConfig { trace: true, ..SolveConfig::default() }).unwrap();
+/// assert_eq!(report.guesses, 0);
+/// assert!(matches!(report.trace[0], TraceEvent::Deduction(Deduction::HiddenSingle { .. })));
+///
+/// let plain = SolveConfig { pipeline: TechniquePipeline::new([Technique::NakedSingle]), ..SolveConfig::default() };
+/// let plain = puzzle.clone().solve_with_report(&plain).unwrap();
+/// assert!(plain.guesses > 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Deduction {
+    /// `at` is the only cell of `unit` that can still hold `digit`
+    HiddenSingle { unit: Unit, digit: u8, at: Point<usize> },
+    /// a naked pair or triple: between them `cells` can only hold `digits`, one each,
+    /// so none of the other cells of `unit` can hold them and `eliminated` are the candidates that removes
+    NakedSubset { unit: Unit, cells: Vec<Point<usize>>, digits: Vec<u8>, eliminated: Vec<(Point<usize>, u8)> },
+    /// a hidden pair or triple: `digits` can only go into `cells` of `unit`, one each,
+    /// so those cells cannot hold anything else and `eliminated` are their other candidates
+    HiddenSubset { unit: Unit, cells: Vec<Point<usize>>, digits: Vec<u8>, eliminated: Vec<(Point<usize>, u8)> },
+    /// pointing: `digit` can only go where `region` meets `line`, so the rest of `line` cannot hold it
+    /// and `eliminated` are the candidates that removes
+    Pointing { region: Unit, line: Unit, digit: u8, eliminated: Vec<(Point<usize>, u8)> },
+    /// claiming: `digit` can only go where `line` meets `region`, so the rest of `region` cannot hold it
+    /// and `eliminated` are the candidates that removes
+    Claiming { line: Unit, region: Unit, digit: u8, eliminated: Vec<(Point<usize>, u8)> },
+    /// a fish, an X-Wing on two `lines`, a Swordfish on three and a Jellyfish on four: in each of the rows (or columns) `digit` can only go
+    /// into `cells`, which all lie on as many columns (or rows) as there are `lines`. Each of those crossing lines
+    /// gets its digit from one of `lines`, so the rest of them cannot hold it and `eliminated` are the candidates
+    /// that removes. For an X-Wing `cells` are the four corners of a rectangle
+    Fish { digit: u8, lines: Vec<Unit>, cells: Vec<Point<usize>>, eliminated: Vec<(Point<usize>, u8)> },
+    /// an XY-Wing: `pivot` can only hold the two `digits` and sees both `pincers`, the first of which can only hold
+    /// the first of `digits` and `digit`, the second the second of them and `digit`. Whichever digit the pivot gets,
+    /// one of the pincers is left with `digit`, so no cell that sees both pincers can hold it and `eliminated`
+    /// are the candidates that removes
+    XYWing { pivot: Point<usize>, pincers: [Point<usize>; 2], digits: [u8; 2], digit: u8, eliminated: Vec<(Point<usize>, u8)> }
+}
+
+impl Deduction {
+    /// the candidates the deduction removes, empty for a hidden single which places a digit instead
+    pub fn eliminated(&self) -> &[(Point<usize>, u8)] {
+        match self {
+            Deduction::HiddenSingle { .. } => &[],
+            Deduction::NakedSubset { eliminated, .. } | Deduction::HiddenSubset { eliminated, .. }
+            | Deduction::Pointing { eliminated, .. } | Deduction::Claiming { eliminated, .. }
+            | Deduction::Fish { eliminated, .. } | Deduction::XYWing { eliminated, .. } => eliminated
+        }
+    }
+
+    /// The technique that made this deduction. A fish on a single line only clears out what a hidden single of that line
+    /// does, so that is what it counts as, and the solver never looks for fish on more than four lines,
+    /// so any bigger one counts as a Jellyfish
+    pub fn technique(&self) -> Technique {
+        match self {
+            Deduction::HiddenSingle { .. } => Technique::HiddenSingle,
+            Deduction::NakedSubset { .. } => Technique::NakedSubset,
+            Deduction::HiddenSubset { .. } => Technique::HiddenSubset,
+            Deduction::Pointing { .. } | Deduction::Claiming { .. } => Technique::LockedCandidates,
+            Deduction::Fish { lines, .. } => match lines.len() {
+                0 | 1 => Technique::HiddenSingle,
+                2 => Technique::XWing,
+                3 => Technique::Swordfish,
+                _ => Technique::Jellyfish
+            },
+            Deduction::XYWing { .. } => Technique::XYWing
+        }
+    }
+}
+
+impl core::fmt::Display for Deduction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Deduction::HiddenSingle { unit, digit, at } => write!(f, "{} is the only place for {} in {}", at.rc(), digit, unit),
+            Deduction::NakedSubset { unit, cells, digits, eliminated } => {
+                write_list(f, cells.iter().map(|cell| cell.rc()))?;
+                write!(f, " can only hold ")?;
+                write_list(f, digits.iter())?;
+                write!(f, " in {}, removing ", unit)?;
+                write_list(f, eliminated.iter().map(|(cell, digit)| format!("{} from {}", digit, cell.rc())))
+            },
+            Deduction::HiddenSubset { unit, cells, digits, eliminated } => {
+                write_list(f, digits.iter())?;
+                write!(f, " can only go in ")?;
+                write_list(f, cells.iter().map(|cell| cell.rc()))?;
+                write!(f, " in {}, removing ", unit)?;
+                write_list(f, eliminated.iter().map(|(cell, digit)| format!("{} from {}", digit, cell.rc())))
+            },
+            Deduction::Pointing { region: unit, line: within, digit, eliminated }
+            | Deduction::Claiming { line: unit, region: within, digit, eliminated } => {
+                write!(f, "{} in {} can only go in {}, removing it from ", digit, unit, within)?;
+                write_list(f, eliminated.iter().map(|(cell, _)| cell.rc()))
+            },
+            Deduction::Fish { digit, lines, cells, eliminated } => {
+                write!(f, "{} in ", digit)?;
+                for (i, line) in lines.iter().enumerate() {
+                    let separator = if i == 0 { "" } else if i + 1 == lines.len() { " and " } else { ", " };
+                    write!(f, "{}{}", separator, line)?;
+                }
+                write!(f, " can only go in ")?;
+                write_list(f, cells.iter().map(|cell| cell.rc()))?;
+                write!(f, ", removing it from ")?;
+                write_list(f, eliminated.iter().map(|(cell, _)| cell.rc()))
+            },
+            Deduction::XYWing { pivot, pincers, digits, digit, eliminated } => {
+                write!(f, "{} can only hold {} or {}, {} can only hold {} or {} and {} can only hold {} or {}, \
+                           so one of {} and {} is {}, removing it from ",
+                       pivot.rc(), digits[0], digits[1], pincers[0].rc(), digits[0], digit, pincers[1].rc(), digits[1], digit,
+                       pincers[0].rc(), pincers[1].rc(), digit)?;
+                write_list(f, eliminated.iter().map(|(cell, _)| cell.rc()))
+            }
+        }
+    }
+}
+
+/// A value that a technique claims has to go into a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForcedPlacement {
+    pub cell: Point<usize>,
+    pub value: u8,
+    pub technique: Technique
+}
+
+/// Two techniques forced different values into the same cell.
+/// On a board that still has a solution this means one of the techniques is wrong,
+/// on a board reached through a wrong guess it only means the board contains a contradiction
+#[derive(Debug, Clone)]
+pub struct TechniquesDisagree {
+    pub first: ForcedPlacement,
+    pub second: ForcedPlacement
+}
+impl core::fmt::Display for TechniquesDisagree {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} places {} in {} but {} places {} there",
+               self.first.technique, self.first.value, self.first.cell.rc(),
+               self.second.technique, self.second.value)
+    }
+}
+
+impl SudokuSolver {
+    /// Every placement that some technique can force on the current board, nothing is collapsed.
+    /// Besides the naked and hidden singles, the first deduction of each of the eliminating techniques forces
+    /// the last candidate of every cell it leaves with only one.
+    /// The same cell can appear multiple times if more than one technique (or unit) forces it
+    ///
+    /// ```
+    /// use sudoku_solver_cli::point::Point;
+    /// use sudoku_solver_cli::sudoku::{ForcedPlacement, SudokuSolver, Technique};
+    ///
+    /// // the naked pair 1, 2 in r1c1 and r1c2 leaves r1c3 with 3
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// candidates[0][0] = vec![1, 2];
+    /// candidates[0][1] = vec![1, 2];
+    /// candidates[0][2] = vec![1, 2, 3];
+    /// let sudoku = SudokuSolver::from_candidates(candidates).unwrap();
+    /// let placement = ForcedPlacement { cell: Point::new(2, 0), value: 3, technique: Technique::NakedSubset };
+    /// assert!(sudoku.forced_placements().contains(&placement));
+    /// assert!(sudoku.audit_forced_placements().is_ok());
+    /// ```
+    pub fn forced_placements(&self) -> Vec<ForcedPlacement> {
+        let mut placements = self.naked_singles();
+        placements.extend(self.hidden_singles());
+        let deductions = [
+            self.find_locked_candidates(), self.find_naked_subset(), self.find_hidden_subset(),
+            self.find_x_wing(), self.find_xy_wing(), self.find_swordfish(), self.find_jellyfish()
+        ];
+        for deduction in deductions.into_iter().flatten() {
+            let eliminated = deduction.eliminated();
+            let mut cells: Vec<Point<usize>> = eliminated.iter().map(|(cell, _)| *cell).collect();
+            cells.sort_by_key(|point| (point.y, point.x));
+            cells.dedup();
+            for cell in cells {
+                let mut left = self.get_cell(&cell).candidates()
+                    .filter(|digit| !eliminated.contains(&(cell, *digit)));
+                if let (Some(value), None) = (left.next(), left.next()) {
+                    placements.push(ForcedPlacement { cell, value, technique: deduction.technique() });
+                }
+            }
+        }
+        placements
+    }
+
+    /// Checks that all techniques that force a value into the same cell, see `forced_placements`, agree on that value
+    pub fn audit_forced_placements(&self) -> Result<(), TechniquesDisagree> {
+        let placements = self.forced_placements();
+        for (i, first) in placements.iter().enumerate() {
+            let disagreeing = placements[i + 1..].iter()
+                .find(|second| second.cell == first.cell && second.value != first.value);
+            if let Some(second) = disagreeing {
+                return Err(TechniquesDisagree { first: *first, second: *second });
+            }
+        }
+        Ok(())
+    }
+
+    /// Every uncollapsed cell with a single candidate left paired with that candidate in row-major order,
+    /// the board is only read so nothing is collapsed. Meant for hints such as "r1c1 has to be 5, it is the only candidate"
+    pub fn naked_single_positions(&self) -> Vec<(Point<usize>, u8)> {
+        self.naked_singles().into_iter().map(|placement| (placement.cell, placement.value)).collect()
+    }
+
+    fn naked_singles(&self) -> Vec<ForcedPlacement> {
+        let mut placements = Vec::new();
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Some(value) = cell.single_candidate() {
+                    placements.push(ForcedPlacement { cell: Point::new(x, y), value, technique: Technique::NakedSingle });
+                }
+            }
+        }
+        placements
+    }
+
+    /// Every digit that only one cell of a row, column or region (or extra region) can still hold, with that cell and
+    /// the unit that makes it a hidden single, e.g. for a hint like "r3c4 is the only place for 7 in row 3".
+    /// The cell can have other candidates, and a cell shows up once per unit it is hidden in. Nothing is collapsed
+    pub fn hidden_single_positions(&self) -> Vec<(Point<usize>, u8, Unit)> {
+        self.hidden_single_deductions().map(|(unit, digit, at)| (at, digit, unit)).collect()
+    }
+
+    fn hidden_singles(&self) -> Vec<ForcedPlacement> {
+        self.hidden_single_deductions()
+            .map(|(_, digit, at)| ForcedPlacement { cell: at, value: digit, technique: Technique::HiddenSingle })
+            .collect()
+    }
+
+    /// every unit with a digit that only one of its cells can still hold, with that digit and cell
+    fn hidden_single_deductions(&self) -> impl Iterator<Item = (Unit, u8, Point<usize>)> + '_ {
+        self.labeled_units().flat_map(move |(label, unit)| {
+            (1..=self.size() as u8).filter_map(move |digit| {
+                if self.unit_contains_digit(&unit, digit) {
+                    return None;
+                }
+                let mut cells_with_digit = unit.iter().filter(|point| self.get_cell(point).contains(digit));
+                match (cells_with_digit.next(), cells_with_digit.next()) {
+                    (Some(cell), None) => Some((label, digit, *cell)),
+                    _ => None
+                }
+            })
+        })
+    }
+
+    /// Collapses hidden singles until there are none left, each one cascades like any other forced placement.
+    /// Nothing is saved since a hidden single holds in every solution of the board
+    pub(super) fn collapse_hidden_singles(&mut self, config: &SolveConfig, report: &mut SolveReport) -> Result<(), Contradiction> {
+        loop {
+            let Some((unit, digit, at)) = self.hidden_single_deductions().next() else { return Ok(()) };
+            if config.trace {
+                report.trace.push(TraceEvent::Deduction(Deduction::HiddenSingle { unit, digit, at }));
+            }
+            self.board[at.y][at.x] = Cell::new_filled(digit);
+            report.forced_placements += 1;
+            report.hidden_singles += 1;
+            self.record_collapse(at, digit, 1, config, report);
+            self.propagate_and_collapse_forced(at, digit, config, report)?;
+        }
+    }
+
+    /// The first naked pair, or naked triple if there are no pairs, that still removes candidates from its unit.
+    /// Nothing is changed, the deduction lists what applying it would remove
+    ///
+    /// Solving this puzzle runs out of singles once, and without locked candidates the naked pair is then
+    /// the only way on without a guess:
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{Deduction, SolveConfig, SudokuSolver, Technique, TechniquePipeline, TraceEvent};
+    ///
+    /// let digits: Vec<u8> = "010030009600100000025000100400900600000000401908000002780002006000007000090008500"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    ///
+    /// let pipeline = TechniquePipeline::default().without(Technique::LockedCandidates);
+    /// let config = SolveConfig { trace: true, pipeline, ..SolveConfig::default() };
+    /// let report = puzzle.clone().solve_with_report(&config).unwrap();
+    /// assert_eq!(report.guesses, 0);
+    /// let pairs: Vec<String> = report.trace.iter().filter_map(|event| match event {
+    ///     TraceEvent::Deduction(pair @ Deduction::NakedSubset { .. }) => Some(pair.to_string()),
+    ///     _ => None
+    /// }).collect();
+    /// assert_eq!(pairs, ["r4c2, r4c6 can only hold 3, 5 in row 4, removing 5 from r4c8, 5 from r4c9"]);
+    ///
+    /// let singles = TechniquePipeline::new([Technique::NakedSingle, Technique::HiddenSingle]);
+    /// let singles_only = SolveConfig { pipeline: singles, ..SolveConfig::default() };
+    /// let singles_only = puzzle.clone().solve_with_report(&singles_only).unwrap();
+    /// assert!(singles_only.guesses > 0);
+    /// ```
+    pub fn find_naked_subset(&self) -> Option<Deduction> {
+        (2..=3).find_map(|subset_size| {
+            self.labeled_units().find_map(|(label, unit)| self.naked_subset_in(label, &unit, subset_size))
+        })
+    }
+
+    /// Applies every naked subset of up to `max_size` cells of every unit, from the smallest size up, and returns how many
+    /// candidates that removed. A single cell with one candidate is a naked single, two cells are a naked pair, three
+    /// a triple and four a quad. Going higher rarely helps on a 9x9 board, the other open cells of a unit with a naked
+    /// subset of five or more form a hidden subset of four or fewer. The cells are not collapsed, even those left with
+    /// a single candidate. Fails if removing candidates leaves a cell with none
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// candidates[0][0] = vec![1, 2];
+    /// candidates[0][1] = vec![1, 2];
+    /// let mut sudoku = SudokuSolver::from_candidates(candidates).unwrap();
+    /// // 1 and 2 leave the other 7 cells of row 1 and the 6 cells of region 1 outside of it
+    /// assert_eq!(sudoku.apply_naked_subsets(4).unwrap(), 26);
+    /// assert_eq!(sudoku.apply_naked_subsets(4).unwrap(), 0);
+    /// ```
+    pub fn apply_naked_subsets(&mut self, max_size: usize) -> Result<usize, SudokuIsUnsolvable> {
+        let mut removed = 0;
+        for subset_size in 1..=max_size {
+            let units: Vec<(Unit, BTreeSet<Point<usize>>)> = self.labeled_units().collect();
+            for (label, unit) in units {
+                while let Some(Deduction::NakedSubset { eliminated, .. }) = self.naked_subset_in(label, &unit, subset_size) {
+                    for (point, digit) in eliminated {
+                        self.get_cell_mut(&point).remove(digit).map_err(|_| SudokuIsUnsolvable)?;
+                        removed += 1;
+                    }
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn naked_subset_in(&self, label: Unit, unit: &BTreeSet<Point<usize>>, subset_size: usize) -> Option<Deduction> {
+        let open: Vec<Point<usize>> = unit.iter().copied().filter(|point| !self.get_cell(point).is_collapsed()).collect();
+        let small: Vec<Point<usize>> = open.iter().copied()
+            .filter(|point| self.get_cell(point).candidates().count() <= subset_size)
+            .collect();
+        (0..small.len()).combinations(subset_size).find_map(|indices| {
+            let cells: Vec<Point<usize>> = indices.into_iter().map(|i| small[i]).collect();
+            let digits: BTreeSet<u8> = cells.iter().flat_map(|cell| self.get_cell(cell).candidates()).collect();
+            // fewer digits than cells is a contradiction, which propagating a guess will run into
+            if digits.len() != subset_size {
+                return None;
+            }
+            let eliminated: Vec<(Point<usize>, u8)> = open.iter()
+                .filter(|point| !cells.contains(point))
+                .flat_map(|point| digits.iter().filter(|digit| self.get_cell(point).contains(**digit)).map(|digit| (*point, *digit)))
+                .collect();
+            (!eliminated.is_empty()).then(|| Deduction::NakedSubset { unit: label, cells, digits: digits.into_iter().collect(), eliminated })
+        })
+    }
+
+    /// The first hidden pair, or hidden triple if there are no pairs, that still removes candidates from its cells.
+    /// Where a naked subset clears its digits out of the rest of the unit, a hidden one clears everything else
+    /// out of its own cells. Nothing is changed, the deduction lists what applying it would remove
+    ///
+    /// On the starting board of this puzzle there is a hidden pair but no naked subset at all,
+    /// and solving it without guessing takes hidden pairs:
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, Technique, TechniquePipeline};
+    ///
+    /// let digits: Vec<u8> = "000002007700000021029000080600030000000600000070950008800320009100706004003009000"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    /// assert_eq!(puzzle.find_naked_subset(), None);
+    /// assert_eq!(puzzle.find_hidden_subset().unwrap().to_string(),
+    ///            "7, 9 can only go in r7c3, r8c2 in region 7, removing 4 from r7c3, 5 from r7c3, 6 from r7c3, 5 from r8c2");
+    ///
+    /// let digits: Vec<u8> = "090000530005407000200009004000000000080003000030000291006080005010690008000300040"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    /// let report = puzzle.clone().solve_with_report(&SolveConfig::default()).unwrap();
+    /// assert_eq!(report.guesses, 0);
+    /// assert!(report.hidden_subsets > 0);
+    /// let pipeline = TechniquePipeline::default().without(Technique::HiddenSubset);
+    /// let without = puzzle.clone().solve_with_report(&SolveConfig { pipeline, ..SolveConfig::default() }).unwrap();
+    /// assert!(without.guesses > 0);
+    /// ```
+    pub fn find_hidden_subset(&self) -> Option<Deduction> {
+        (2..=3).find_map(|subset_size| {
+            self.labeled_units().find_map(|(label, unit)| self.hidden_subset_in(label, &unit, subset_size))
+        })
+    }
+
+    fn hidden_subset_in(&self, label: Unit, unit: &BTreeSet<Point<usize>>, subset_size: usize) -> Option<Deduction> {
+        // every digit still to be placed in the unit with the cells that can hold it
+        let places: Vec<(u8, Vec<Point<usize>>)> = self.missing_digits_in_unit(unit).into_iter()
+            .map(|digit| (digit, unit.iter().copied().filter(|point| self.get_cell(point).contains(digit)).collect::<Vec<_>>()))
+            .filter(|(_, cells)| cells.len() <= subset_size)
+            .collect();
+        (0..places.len()).combinations(subset_size).find_map(|indices| {
+            let digits: Vec<u8> = indices.iter().map(|i| places[*i].0).collect();
+            let mut cells: Vec<Point<usize>> = indices.iter().flat_map(|i| places[*i].1.iter().copied()).collect();
+            cells.sort_by_key(|point| (point.y, point.x));
+            cells.dedup();
+            // more digits than cells is a contradiction, which propagating a guess will run into
+            if cells.len() != subset_size {
+                return None;
+            }
+            let eliminated: Vec<(Point<usize>, u8)> = cells.iter()
+                .flat_map(|point| self.get_cell(point).candidates().filter(|digit| !digits.contains(digit)).map(|digit| (*point, digit)))
+                .collect();
+            (!eliminated.is_empty()).then_some(Deduction::HiddenSubset { unit: label, cells, digits, eliminated })
+        })
+    }
+
+    /// The first pointing or, if there is none, claiming pair or triple that still removes candidates.
+    /// Pointing is a digit of a region that can only go into one of its rows or columns, which leaves the rest of that line
+    /// without it. Claiming is the converse, a digit of a row or column that can only go into one region.
+    /// Only the regions of the board take part, not the extra regions of variants. Nothing is changed
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{Deduction, SudokuSolver, Unit};
+    ///
+    /// let mut pointing = [[0; 9]; 9];
+    /// pointing[1][..3].copy_from_slice(&[2, 3, 4]);
+    /// pointing[2][..3].copy_from_slice(&[5, 6, 7]);
+    /// let pointing = SudokuSolver::new(pointing).unwrap().find_locked_candidates().unwrap();
+    /// assert!(matches!(pointing, Deduction::Pointing { region: Unit::Region(0), line: Unit::Row(0), digit: 1, .. }));
+    /// assert_eq!(pointing.to_string(), "1 in region 1 can only go in row 1, removing it from r1c4, r1c5, r1c6, r1c7, r1c8, r1c9");
+    ///
+    /// let mut claiming = [[0; 9]; 9];
+    /// claiming[0][3..].copy_from_slice(&[2, 3, 4, 5, 6, 7]);
+    /// let claiming = SudokuSolver::new(claiming).unwrap().find_locked_candidates().unwrap();
+    /// assert!(matches!(claiming, Deduction::Claiming { line: Unit::Row(0), region: Unit::Region(0), digit: 1, .. }));
+    /// assert_eq!(claiming.to_string(), "1 in row 1 can only go in region 1, removing it from r2c1, r2c2, r2c3, r3c1, r3c2, r3c3");
+    /// ```
+    pub fn find_locked_candidates(&self) -> Option<Deduction> {
+        let lines: Vec<(Unit, BTreeSet<Point<usize>>)> = (0..self.size()).map(|y| (Unit::Row(y), self.get_row(y)))
+            .chain((0..self.size()).map(|x| (Unit::Column(x), self.get_column(x))))
+            .collect();
+        let regions: Vec<(Unit, BTreeSet<Point<usize>>)> = self.get_regions().into_iter().enumerate()
+            .map(|(i, region)| (Unit::Region(i), region))
+            .collect();
+        let pointing = regions.iter().find_map(|(region_label, region)| lines.iter().find_map(|(line_label, line)| {
+            self.locked_digit(region, line)
+                .map(|eliminated| Deduction::Pointing { region: *region_label, line: *line_label, digit: eliminated[0].1, eliminated })
+        }));
+        pointing.or_else(|| lines.iter().find_map(|(line_label, line)| regions.iter().find_map(|(region_label, region)| {
+            self.locked_digit(line, region)
+                .map(|eliminated| Deduction::Claiming { line: *line_label, region: *region_label, digit: eliminated[0].1, eliminated })
+        })))
+    }
+
+    /// Applies every pointing and claiming of `find_locked_candidates` until there are none left, which is box-line
+    /// reduction in both directions, and returns how many candidates that removed. Cells left with a single candidate
+    /// are not collapsed. Fails if removing candidates leaves a cell with none
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// let mut claiming = [[0; 9]; 9];
+    /// claiming[0][3..].copy_from_slice(&[2, 3, 4, 5, 6, 7]);
+    /// let mut sudoku = SudokuSolver::new(claiming).unwrap();
+    /// // 1, 8 and 9 of row 1 can only go into region 1 and get removed from the 6 cells below them
+    /// assert_eq!(sudoku.apply_box_line_reduction().unwrap(), 18);
+    /// assert_eq!(sudoku.find_locked_candidates(), None);
+    /// ```
+    pub fn apply_box_line_reduction(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        let mut removed = 0;
+        while let Some(Deduction::Pointing { eliminated, .. } | Deduction::Claiming { eliminated, .. }) = self.find_locked_candidates() {
+            for (point, digit) in eliminated {
+                self.get_cell_mut(&point).remove(digit).map_err(|_| SudokuIsUnsolvable)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// the candidates removed from the rest of `other` by the first digit that can only go where `unit` meets `other`
+    fn locked_digit(&self, unit: &BTreeSet<Point<usize>>, other: &BTreeSet<Point<usize>>) -> Option<Vec<(Point<usize>, u8)>> {
+        if unit.is_disjoint(other) {
+            return None;
+        }
+        self.missing_digits_in_unit(unit).into_iter().find_map(|digit| {
+            let mut places = unit.iter().filter(|point| self.get_cell(point).contains(digit)).peekable();
+            // no place at all is a contradiction, which propagating a guess will run into
+            if places.peek().is_none() || !places.all(|point| other.contains(point)) {
+                return None;
+            }
+            let mut eliminated: Vec<(Point<usize>, u8)> = other.iter()
+                .filter(|point| !unit.contains(point) && self.get_cell(point).contains(digit))
+                .map(|point| (*point, digit))
+                .collect();
+            eliminated.sort_by_key(|(point, _)| (point.y, point.x));
+            (!eliminated.is_empty()).then_some(eliminated)
+        })
+    }
+
+    /// The first X-Wing that still removes candidates, going through the digits from 1 up and trying X-Wings
+    /// with two rows as their base before those with two columns. Nothing is changed
+    ///
+    /// Both of these only solve without guessing thanks to an X-Wing, the first one on two rows and the second on two columns:
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, Technique, TechniquePipeline};
+    ///
+    /// let examples = [
+    ///     ("100000569492056108056109240009640801064010000218035604040500016905061402621000005",
+    ///      "7 in row 2 and row 6 can only go in r2c4, r2c8, r6c4, r6c8, \
+    ///       removing it from r1c4, r4c8, r5c4, r5c8, r8c4, r8c8, r9c4, r9c8"),
+    ///     ("980062753065003000327050006790030500050009000832045009673591428249087005518020007",
+    ///      "1 in column 1 and column 5 can only go in r2c1, r2c5, r5c1, r5c5, \
+    ///       removing it from r2c4, r2c7, r2c8, r2c9, r5c3, r5c4, r5c7, r5c8, r5c9")
+    /// ];
+    /// for (puzzle, x_wing) in examples {
+    ///     let digits: Vec<u8> = puzzle.bytes().map(|digit| digit - b'0').collect();
+    ///     let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    ///     assert_eq!(puzzle.find_x_wing().unwrap().to_string(), x_wing);
+    ///
+    ///     assert_eq!(puzzle.clone().solve_with_report(&SolveConfig::default()).unwrap().guesses, 0);
+    ///     let pipeline = TechniquePipeline::new(Technique::ALL.into_iter().take_while(|technique| *technique != Technique::XWing));
+    ///     let without = SolveConfig { pipeline, ..SolveConfig::default() };
+    ///     assert!(puzzle.clone().solve_with_report(&without).unwrap().guesses > 0);
+    /// }
+    /// ```
+    pub fn find_x_wing(&self) -> Option<Deduction> {
+        self.find_fish(2)
+    }
+
+    /// The first Swordfish that still removes candidates, in the same order as `find_x_wing`. Nothing is changed
+    ///
+    /// This one only solves without guessing thanks to a Swordfish on three columns, found once singles
+    /// and the easier eliminations have run out:
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{Deduction, SolveConfig, SudokuSolver, Technique, TechniquePipeline, TraceEvent};
+    ///
+    /// let digits: Vec<u8> = "108000067000050000000000030006100040450000900000093000200040010003002700807001005"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    /// let report = puzzle.clone().solve_with_report(&SolveConfig { trace: true, ..SolveConfig::default() }).unwrap();
+    /// let swordfish = report.trace.iter().find_map(|event| match event {
+    ///     TraceEvent::Deduction(fish @ Deduction::Fish { .. }) if fish.technique() == Technique::Swordfish => Some(fish),
+    ///     _ => None
+    /// });
+    /// assert_eq!(swordfish.unwrap().to_string(),
+    ///            "9 in column 3, column 6 and column 9 can only go in r2c3, r2c6, r2c9, r3c3, r3c6, r3c9, r7c3, r7c6, r7c9, \
+    ///             removing it from r2c1, r2c2, r2c4, r2c8, r3c1, r3c2, r3c4, r7c2, r7c4");
+    /// assert_eq!(report.guesses, 0);
+    /// assert_eq!(report.hardest_technique(), Some(Technique::Swordfish));
+    ///
+    /// let pipeline = TechniquePipeline::default().without(Technique::Swordfish).without(Technique::Jellyfish);
+    /// let without = SolveConfig { pipeline, ..SolveConfig::default() };
+    /// let without = puzzle.clone().solve_with_report(&without).unwrap();
+    /// assert!(without.guesses > 0);
+    /// ```
+    pub fn find_swordfish(&self) -> Option<Deduction> {
+        self.find_fish(3)
+    }
+
+    /// The first Jellyfish that still removes candidates, in the same order as `find_x_wing`. Nothing is changed
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SudokuSolver, Technique};
+    ///
+    /// // 1 can only go into the first four columns of the first four rows, and any two or three of those rows
+    /// // spread it over all four columns, so only all four rows together clear it out of the rest of the columns
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// for y in 0..4 {
+    ///     for x in 4..9 {
+    ///         candidates[y][x] = (2..=9).collect();
+    ///     }
+    /// }
+    /// let sudoku = SudokuSolver::from_candidates(candidates).unwrap();
+    /// assert_eq!(sudoku.find_x_wing(), None);
+    /// assert_eq!(sudoku.find_swordfish(), None);
+    /// let jellyfish = sudoku.find_jellyfish().unwrap();
+    /// assert_eq!(jellyfish.technique(), Technique::Jellyfish);
+    /// assert_eq!(jellyfish.eliminated().len(), 20);
+    /// ```
+    pub fn find_jellyfish(&self) -> Option<Deduction> {
+        self.find_fish(4)
+    }
+
+    /// the first fish on `size` lines, going through the digits from 1 up and trying rows as the base before columns
+    fn find_fish(&self, size: usize) -> Option<Deduction> {
+        (1..=self.size() as u8).find_map(|digit| self.fish_of(digit, size, true).or_else(|| self.fish_of(digit, size, false)))
+    }
+
+    /// Applies every fish on up to `max_size` lines for every digit, from the smallest size up and trying rows as the base
+    /// before columns, and returns how many candidates that removed. Two lines are an X-Wing, three a Swordfish and four
+    /// a Jellyfish, while a single line is a hidden single of that line clearing its digit out of the crossing line.
+    /// Like with `apply_naked_subsets`, going higher rarely helps since a fish on five or more lines of a 9x9 board comes
+    /// with a smaller one on the other lines. Cells left with a single candidate are not collapsed.
+    /// Fails if removing candidates leaves a cell with none
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// // 1 can only go into columns 1 and 2 of rows 1 and 2, an X-Wing that clears it out of the rest of both columns
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// for x in 2..9 {
+    ///     candidates[0][x] = (2..=9).collect();
+    ///     candidates[1][x] = (2..=9).collect();
+    /// }
+    /// let mut sudoku = SudokuSolver::from_candidates(candidates).unwrap();
+    /// assert_eq!(sudoku.apply_fish_patterns(4).unwrap(), 14);
+    /// assert_eq!(sudoku.apply_fish_patterns(4).unwrap(), 0);
+    /// ```
+    pub fn apply_fish_patterns(&mut self, max_size: usize) -> Result<usize, SudokuIsUnsolvable> {
+        let mut removed = 0;
+        for size in 1..=max_size {
+            for digit in 1..=self.size() as u8 {
+                for by_rows in [true, false] {
+                    while let Some(Deduction::Fish { eliminated, .. }) = self.fish_of(digit, size, by_rows) {
+                        for (point, digit) in eliminated {
+                            self.get_cell_mut(&point).remove(digit).map_err(|_| SudokuIsUnsolvable)?;
+                            removed += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// a fish of `digit` on `size` lines, with rows as its base if `by_rows`, otherwise with columns
+    fn fish_of(&self, digit: u8, size: usize, by_rows: bool) -> Option<Deduction> {
+        let board_size = self.size();
+        // the cell `offset` cells along `line`
+        let at = |line: usize, offset: usize| if by_rows { Point::new(offset, line) } else { Point::new(line, offset) };
+        let holds_digit = |point: &Point<usize>| !self.get_cell(point).is_collapsed() && self.get_cell(point).contains(digit);
+        let label = |line: usize| if by_rows { Unit::Row(line) } else { Unit::Column(line) };
+
+        // every line where at most `size` cells can hold the digit, with how far along the line they are
+        let candidates: Vec<(usize, Vec<usize>)> = (0..board_size)
+            .map(|line| (line, (0..board_size).filter(|offset| holds_digit(&at(line, *offset))).collect::<Vec<_>>()))
+            .filter(|(_, offsets)| (1..=size).contains(&offsets.len()))
+            .collect();
+
+        (0..candidates.len()).combinations(size).find_map(|indices| {
+            let lines: Vec<usize> = indices.iter().map(|i| candidates[*i].0).collect();
+            let crossing: BTreeSet<usize> = indices.iter().flat_map(|i| candidates[*i].1.iter().copied()).collect();
+            // fewer crossing lines than lines is a contradiction, which propagating a guess will run into
+            if crossing.len() != size {
+                return None;
+            }
+            let mut eliminated: Vec<(Point<usize>, u8)> = crossing.iter()
+                .flat_map(|offset| (0..board_size).filter(|line| !lines.contains(line)).map(move |line| at(line, *offset)))
+                .filter(holds_digit)
+                .map(|point| (point, digit))
+                .collect();
+            eliminated.sort_by_key(|(point, _)| (point.y, point.x));
+            let mut cells: Vec<Point<usize>> = indices.iter()
+                .flat_map(|i| {
+                    let (line, offsets) = &candidates[*i];
+                    offsets.iter().map(move |offset| at(*line, *offset))
+                })
+                .collect();
+            cells.sort_by_key(|point| (point.y, point.x));
+            (!eliminated.is_empty()).then(|| Deduction::Fish { digit, lines: lines.into_iter().map(label).collect(), cells, eliminated })
+        })
+    }
+
+    /// The uncollapsed cells left with exactly two candidates in row-major order, with those candidates from the smallest up.
+    /// Nothing is changed
+    pub fn bivalue_cells(&self) -> Vec<(Point<usize>, [u8; 2])> {
+        let size = self.size();
+        (0..size * size).map(|i| Point::from_index(i, size)).filter_map(|point| {
+            let candidates = self.get_candidates(&point);
+            let mut candidates = candidates.iter().copied();
+            match (candidates.next(), candidates.next(), candidates.next()) {
+                (Some(first), Some(second), None) if !self.get_cell(&point).is_collapsed() => Some((point, [first, second])),
+                _ => None
+            }
+        }).collect()
+    }
+
+    /// The first XY-Wing that still removes candidates, trying the bivalue cells as the pivot in row-major order.
+    /// Nothing is changed
+    ///
+    /// This one only solves without guessing thanks to an XY-Wing:
+    /// ```
+    /// use sudoku_solver_cli::sudoku::{SolveConfig, SudokuSolver, Technique, TechniquePipeline};
+    ///
+    /// let digits: Vec<u8> = "900240000050690231020050090090700320002935607070002900069020073510079062207086009"
+    ///     .bytes().map(|digit| digit - b'0').collect();
+    /// let puzzle = SudokuSolver::from_flat(&digits).unwrap();
+    /// assert_eq!(puzzle.find_xy_wing().unwrap().to_string(),
+    ///            "r1c2 can only hold 3 or 8, r9c2 can only hold 3 or 4 and r2c3 can only hold 8 or 4, \
+    ///             so one of r9c2 and r2c3 is 4, removing it from r8c3");
+    ///
+    /// let report = puzzle.clone().solve_with_report(&SolveConfig::default()).unwrap();
+    /// assert_eq!(report.guesses, 0);
+    /// assert_eq!(report.hardest_technique(), Some(Technique::XYWing));
+    /// let without = SolveConfig { pipeline: TechniquePipeline::default().without(Technique::XYWing), ..SolveConfig::default() };
+    /// assert!(puzzle.clone().solve_with_report(&without).unwrap().guesses > 0);
+    /// ```
+    pub fn find_xy_wing(&self) -> Option<Deduction> {
+        let bivalue = self.bivalue_cells();
+        let sees = |a: Point<usize>, b: Point<usize>| self.get_peers(a).contains(&b);
+        bivalue.iter().find_map(|&(pivot, [x, y])| {
+            let wings: Vec<(Point<usize>, [u8; 2])> = bivalue.iter().copied().filter(|(cell, _)| sees(pivot, *cell)).collect();
+            // a pincer shares one digit of the pivot, the other one is the digit both pincers have in common
+            let other = |candidates: [u8; 2], shared: u8| match candidates {
+                [first, second] if first == shared && second != x && second != y => Some(second),
+                [first, second] if second == shared && first != x && first != y => Some(first),
+                _ => None
+            };
+            wings.iter().find_map(|&(first, first_candidates)| {
+                let digit = other(first_candidates, x)?;
+                wings.iter().filter(|(second, candidates)| *second != first && other(*candidates, y) == Some(digit))
+                    .find_map(|&(second, _)| {
+                        let mut eliminated: Vec<(Point<usize>, u8)> = self.get_peers(first).iter().copied()
+                            .filter(|cell| *cell != second && sees(second, *cell))
+                            .filter(|cell| !self.get_cell(cell).is_collapsed() && self.get_cell(cell).contains(digit))
+                            .map(|cell| (cell, digit))
+                            .collect();
+                        eliminated.sort_by_key(|(point, _)| (point.y, point.x));
+                        (!eliminated.is_empty())
+                            .then_some(Deduction::XYWing { pivot, pincers: [first, second], digits: [x, y], digit, eliminated })
+                    })
+            })
+        })
+    }
+
+    /// Applies one deduction of the first technique of `pipeline` that has one, by default pointing or claiming,
+    /// or naked subset if there are none, or hidden subset if there are none of those either, or X-Wing, XY-Wing, Swordfish or Jellyfish
+    /// as the last resort, and returns whether there was one. Cells it leaves with a single candidate
+    /// are collapsed and cascade like any other forced placement
+    pub(super) fn eliminate_candidates(&mut self, pipeline: &TechniquePipeline, config: &SolveConfig, report: &mut SolveReport) -> Result<bool, Contradiction> {
+        let deduction = pipeline.techniques().iter().find_map(|technique| match technique {
+            Technique::LockedCandidates => self.find_locked_candidates(),
+            Technique::NakedSubset => self.find_naked_subset(),
+            Technique::HiddenSubset => self.find_hidden_subset(),
+            Technique::XWing => self.find_x_wing(),
+            Technique::XYWing => self.find_xy_wing(),
+            Technique::Swordfish => self.find_swordfish(),
+            Technique::Jellyfish => self.find_jellyfish(),
+            // the singles are not eliminations, they are collapsed before anything here runs
+            Technique::NakedSingle | Technique::HiddenSingle => None
+        });
+        // the cells that hold the digits being removed somewhere else, one of them is the source of a contradiction
+        let (cells, eliminated) = match &deduction {
+            Some(Deduction::Pointing { region: unit, line: other, digit, eliminated })
+            | Some(Deduction::Claiming { line: unit, region: other, digit, eliminated }) => {
+                report.locked_candidates += 1;
+                let (unit, other) = (self.unit_cells(*unit), self.unit_cells(*other));
+                let cells = unit.intersection(&other).copied().filter(|point| self.get_cell(point).contains(*digit)).collect();
+                (cells, eliminated.clone())
+            },
+            Some(Deduction::NakedSubset { cells, eliminated, .. }) => {
+                report.naked_subsets += 1;
+                (cells.clone(), eliminated.clone())
+            },
+            Some(Deduction::HiddenSubset { cells, eliminated, .. }) => {
+                report.hidden_subsets += 1;
+                (cells.clone(), eliminated.clone())
+            },
+            Some(fish @ Deduction::Fish { cells, eliminated, .. }) => {
+                match fish.technique() {
+                    Technique::XWing => report.x_wings += 1,
+                    Technique::Swordfish => report.swordfish += 1,
+                    _ => report.jellyfish += 1
+                }
+                (cells.clone(), eliminated.clone())
+            },
+            Some(Deduction::XYWing { pivot, pincers, eliminated, .. }) => {
+                report.xy_wings += 1;
+                (vec![*pivot, pincers[0], pincers[1]], eliminated.clone())
+            },
+            _ => return Ok(false)
+        };
+        if config.trace {
+            report.trace.extend(deduction.map(TraceEvent::Deduction));
+        }
+
+        for &(point, digit) in &eliminated {
+            // the other eliminations can empty a cell, a hidden subset only removes candidates its cells can spare
+            let source = cells.iter().copied()
+                .find(|cell| self.get_peers(point).contains(cell) && self.get_cell(cell).contains(digit))
+                .unwrap_or(point);
+            self.get_cell_mut(&point).remove(digit)
+                .map_err(|_| Contradiction::NoCandidatesLeft { source, cell: point, digit })?;
+        }
+        for &(point, _) in &eliminated {
+            let Some(forced) = self.get_cell(&point).single_candidate() else { continue };
+            self.board[point.y][point.x].collapse(&self.collapse_order);
+            report.forced_placements += 1;
+            self.record_collapse(point, forced, 1, config, report);
+            self.propagate_and_collapse_forced(point, forced, config, report)?;
+        }
+        Ok(true)
+    }
+
+    /// the cells of `unit`
+    fn unit_cells(&self, unit: Unit) -> BTreeSet<Point<usize>> {
+        match unit {
+            Unit::Row(y) => self.get_row(y),
+            Unit::Column(x) => self.get_column(x),
+            Unit::Region(i) => self.get_regions().swap_remove(i),
+            Unit::Extra(i) => self.extra_regions[i].clone()
+        }
+    }
+
+    /// `all_units` and then any extra regions of the variant, each with the `Unit` it is
+    fn labeled_units(&self) -> impl Iterator<Item = (Unit, BTreeSet<Point<usize>>)> + '_ {
+        let labels = (0..self.size()).map(Unit::Row)
+            .chain((0..self.size()).map(Unit::Column))
+            .chain((0..self.size()).map(Unit::Region));
+        labels.zip(self.all_units())
+            .chain(self.extra_regions.iter().cloned().enumerate().map(|(i, region)| (Unit::Extra(i), region)))
+    }
+}
+
+/// writes `items` separated by commas
+fn write_list(f: &mut core::fmt::Formatter<'_>, items: impl Iterator<Item = impl core::fmt::Display>) -> core::fmt::Result {
+    for (i, item) in items.enumerate() {
+        write!(f, "{}{}", if i == 0 { "" } else { ", " }, item)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::point::Point;
+use super::{ErrorSudokuContainsAContradiction, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::vec::Vec;
+
+/// Thermometer sudoku, digits on a thermometer strictly increase from the bulb to the tip
+#[derive(Debug, Clone)]
+pub struct ThermometerSudokuSolver {
+    solver: SudokuSolver,
+    /// each thermometer lists its cells starting at the bulb
+    thermometers: Vec<Vec<Point<usize>>>
+}
+
+impl ThermometerSudokuSolver {
+    pub fn new(starting_state: [[u8; 9]; 9], thermometers: Vec<Vec<Point<usize>>>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Ok(ThermometerSudokuSolver {
+            solver: SudokuSolver::new(starting_state)?,
+            thermometers
+        })
+    }
+
+    pub fn solver(&self) -> &SudokuSolver {
+        &self.solver
+    }
+
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        let thermometers = self.thermometers.clone();
+        self.solver.solve_with_strategy(&mut |solver| apply_thermometer_constraints(solver, &thermometers).map(|_| ()))
+    }
+
+    /// Removes candidates that are not above the lowest candidate of the previous cell
+    /// or not below the highest candidate of the next cell, returns how many candidates were removed
+    pub fn apply_thermometer_constraints(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        apply_thermometer_constraints(&mut self.solver, &self.thermometers).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.solver.check_if_correct() && self.thermometers.iter().all(|thermometer| {
+            thermometer.windows(2).all(|pair| {
+                match (self.solver.get_cell(&pair[0]).value(), self.solver.get_cell(&pair[1]).value()) {
+                    (Some(lower), Some(higher)) => lower < higher,
+                    _ => false
+                }
+            })
+        })
+    }
+}
+
+impl core::fmt::Display for ThermometerSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.solver.fmt(f)
+    }
+}
+
+fn apply_thermometer_constraints(solver: &mut SudokuSolver, thermometers: &[Vec<Point<usize>>]) -> Result<usize, ()> {
+    let mut removed = 0;
+    for thermometer in thermometers {
+        for i in 0..thermometer.len() {
+            let candidates = solver.get_candidates(&thermometer[i]);
+            let above = match i.checked_sub(1) {
+                Some(previous) => solver.get_candidates(&thermometer[previous]).into_iter().min().unwrap_or(9),
+                None => 0
+            };
+            let below = match thermometer.get(i + 1) {
+                Some(next) => solver.get_candidates(next).into_iter().max().unwrap_or(1),
+                None => 10
+            };
+            for value in candidates.into_iter().filter(|value| *value <= above || *value >= below) {
+                solver.get_cell_mut(&thermometer[i]).remove(value)?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
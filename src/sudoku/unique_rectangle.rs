@@ -0,0 +1,177 @@
+use crate::point::Point;
+use super::{SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use itertools::Itertools;
+
+impl SudokuSolver {
+    /// Applies unique rectangles until there are none left and returns how many candidates that removed.
+    /// Four open cells on two rows and two columns that lie in exactly two regions and could all only hold the same
+    /// two digits would be a deadly pattern: the digits could be swapped, so a puzzle with a unique solution cannot
+    /// end up there and whatever leads into it is ruled out. With the two digits `a` and `b` of the rectangle,
+    /// the types as Andrew Stuart describes them are
+    /// - Type 1: three corners can only hold `a` and `b`, so the fourth is not either of them
+    /// - Type 2: two corners can only hold `a` and `b` and the other two only `a`, `b` and the same third digit,
+    ///   one of them is that digit so no cell that sees both of them can hold it
+    /// - Type 3: the other two corners share a unit and their extra digits act as a single cell of it,
+    ///   which can form a naked subset with other cells of the unit and clear its digits out of the rest of it
+    /// - Type 4: the other two corners share a unit where `a` can only go into them, one of them is `a`
+    ///   so neither can be `b`
+    ///
+    /// This is only sound for puzzles with a unique solution, which is why solving never applies it: on a puzzle
+    /// with several it can remove the candidates of real solutions. Extra regions of variants may not allow the digits
+    /// of a rectangle to be swapped, so nothing is removed on them. Cells left with a single candidate are not collapsed.
+    /// Fails if removing candidates leaves a cell with none
+    ///
+    /// ```
+    /// use sudoku_solver_cli::sudoku::SudokuSolver;
+    ///
+    /// // r1c1, r1c4 and r2c1 can only hold 1 and 2, so r2c4 cannot hold either (type 1)
+    /// let mut candidates: [[Vec<u8>; 9]; 9] = core::array::from_fn(|_| core::array::from_fn(|_| (1..=9).collect()));
+    /// for (y, x) in [(0, 0), (0, 3), (1, 0)] {
+    ///     candidates[y][x] = vec![1, 2];
+    /// }
+    /// candidates[1][3] = vec![1, 2, 3];
+    /// let mut type_1 = SudokuSolver::from_candidates(candidates.clone()).unwrap();
+    /// assert_eq!(type_1.apply_unique_rectangle().unwrap(), 2);
+    /// assert_eq!(type_1.entropy_map()[1][3], 1);
+    ///
+    /// // r2c1 or r2c4 is 7, which the rest of row 2 cannot hold then (type 2)
+    /// candidates[1][0] = vec![1, 2, 7];
+    /// candidates[1][3] = vec![1, 2, 7];
+    /// let mut type_2 = SudokuSolver::from_candidates(candidates.clone()).unwrap();
+    /// assert_eq!(type_2.apply_unique_rectangle().unwrap(), 7);
+    ///
+    /// // 1 can only go into r2c1 and r2c4 in row 2, so neither of them can be 2 (type 4)
+    /// candidates[1][0] = vec![1, 2, 5];
+    /// candidates[1][3] = vec![1, 2, 6];
+    /// for x in [1, 2, 4, 5, 6, 7, 8] {
+    ///     candidates[1][x] = (2..=9).collect();
+    /// }
+    /// let mut type_4 = SudokuSolver::from_candidates(candidates).unwrap();
+    /// assert_eq!(type_4.apply_unique_rectangle().unwrap(), 2);
+    /// assert_eq!(type_4.entropy_map()[1][..4], [2, 8, 8, 2]);
+    /// ```
+    pub fn apply_unique_rectangle(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        if !self.extra_regions.is_empty() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        while let Some(eliminated) = self.unique_rectangle() {
+            for (point, digit) in eliminated {
+                self.get_cell_mut(&point).remove(digit).map_err(|_| SudokuIsUnsolvable)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// the candidates removed by the first unique rectangle that still removes any, going through the rectangles
+    /// by their top row, bottom row, left column and right column and trying the types in order
+    fn unique_rectangle(&self) -> Option<Vec<(Point<usize>, u8)>> {
+        let size = self.size();
+        let rectangles = (0..size).flat_map(move |top| (top + 1..size).map(move |bottom| (top, bottom)))
+            .flat_map(move |(top, bottom)| (0..size).flat_map(move |left| (left + 1..size).map(move |right| (top, bottom, left, right))));
+        rectangles.filter_map(|(top, bottom, left, right)| {
+            let corners = [Point::new(left, top), Point::new(right, top), Point::new(left, bottom), Point::new(right, bottom)];
+            let regions: BTreeSet<usize> = corners.iter().map(|corner| self.region_index(*corner)).collect();
+            if regions.len() != 2 || corners.iter().any(|corner| self.get_cell(corner).is_collapsed()) {
+                return None;
+            }
+            let candidates = corners.map(|corner| self.get_candidates(&corner));
+            let common: Vec<u8> = candidates[0].iter().copied()
+                .filter(|digit| candidates[1..].iter().all(|cell| cell.contains(digit)))
+                .collect();
+            let pairs = (0..common.len()).flat_map(|i| (i + 1..common.len()).map(move |j| (i, j)));
+            pairs.map(|(i, j)| self.unique_rectangle_of(corners, [common[i], common[j]])).find(|eliminated| !eliminated.is_empty())
+        }).next()
+    }
+
+    /// what the rectangle with `corners` and the two `digits` all of them can hold removes, empty if nothing
+    fn unique_rectangle_of(&self, corners: [Point<usize>; 4], digits: [u8; 2]) -> Vec<(Point<usize>, u8)> {
+        let holds_only_digits = |point: &Point<usize>| self.get_cell(point).candidates().all(|digit| digits.contains(&digit));
+        let (floor, roof): (Vec<Point<usize>>, Vec<Point<usize>>) = corners.into_iter().partition(holds_only_digits);
+        let holds = |point: &Point<usize>, digit: u8| !self.get_cell(point).is_collapsed() && self.get_cell(point).contains(digit);
+        let mut eliminated = match (floor.len(), roof.as_slice()) {
+            (3, [roof]) => digits.iter().map(|digit| (*roof, *digit)).collect(),
+            (2, [first, second]) => {
+                let extra = |point: &Point<usize>| -> BTreeSet<u8> {
+                    self.get_cell(point).candidates().filter(|digit| !digits.contains(digit)).collect()
+                };
+                let extras: BTreeSet<u8> = extra(first).union(&extra(second)).copied().collect();
+                let units = self.shared_units(*first, *second);
+
+                // type 2
+                let mut eliminated: Vec<(Point<usize>, u8)> = Vec::new();
+                // both corners have an extra digit, so a single one between them is the same for both
+                if let (1, Some(digit)) = (extras.len(), extras.first().copied()) {
+                    eliminated = self.get_peers(*first).iter().copied()
+                        .filter(|cell| *cell != *second && self.get_peers(*second).contains(cell) && holds(cell, digit))
+                        .map(|cell| (cell, digit))
+                        .collect();
+                }
+                // type 3
+                if eliminated.is_empty() {
+                    eliminated = units.iter()
+                        .map(|unit| self.pseudo_cell_subset(unit, [*first, *second], &extras))
+                        .find(|eliminated| !eliminated.is_empty())
+                        .unwrap_or_default();
+                }
+                // type 4
+                if eliminated.is_empty() {
+                    eliminated = units.iter().find_map(|unit| {
+                        let [a, b] = digits;
+                        let locked = |digit: u8| unit.iter().all(|cell| *cell == *first || *cell == *second || !holds(cell, digit));
+                        let removed = if locked(a) { b } else if locked(b) { a } else { return None };
+                        Some(vec![(*first, removed), (*second, removed)])
+                    }).unwrap_or_default();
+                }
+                eliminated
+            },
+            _ => Vec::new()
+        };
+        eliminated.sort_by_key(|(point, digit)| (point.y, point.x, *digit));
+        eliminated
+    }
+
+    /// the rows, columns and regions both `first` and `second` are in
+    fn shared_units(&self, first: Point<usize>, second: Point<usize>) -> Vec<BTreeSet<Point<usize>>> {
+        let mut units = Vec::new();
+        if first.y == second.y {
+            units.push(self.get_row(first.y));
+        }
+        if first.x == second.x {
+            units.push(self.get_column(first.x));
+        }
+        if self.region_index(first) == self.region_index(second) {
+            units.push(self.get_region(first));
+        }
+        units
+    }
+
+    /// `roof` taken together as one cell of `unit` that holds one of `extras`: the candidates removed from the rest of
+    /// `unit` by the first naked subset of up to 3 other cells that forms with them, empty if there is none
+    fn pseudo_cell_subset(&self, unit: &BTreeSet<Point<usize>>, roof: [Point<usize>; 2], extras: &BTreeSet<u8>) -> Vec<(Point<usize>, u8)> {
+        let open: Vec<Point<usize>> = unit.iter().copied()
+            .filter(|point| !roof.contains(point) && !self.get_cell(point).is_collapsed())
+            .collect();
+        (1..=3).find_map(|others| {
+            let small: Vec<Point<usize>> = open.iter().copied()
+                .filter(|point| self.get_cell(point).candidates().count() <= others + 1)
+                .collect();
+            (0..small.len()).combinations(others).find_map(|indices| {
+                let cells: Vec<Point<usize>> = indices.into_iter().map(|i| small[i]).collect();
+                let mut subset = extras.clone();
+                subset.extend(cells.iter().flat_map(|cell| self.get_cell(cell).candidates()));
+                if subset.len() != others + 1 {
+                    return None;
+                }
+                let eliminated: Vec<(Point<usize>, u8)> = open.iter()
+                    .filter(|point| !cells.contains(point))
+                    .flat_map(|point| subset.iter().filter(|digit| self.get_cell(point).contains(**digit)).map(|digit| (*point, *digit)))
+                    .collect();
+                (!eliminated.is_empty()).then_some(eliminated)
+            })
+        }).unwrap_or_default()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::point::Point;
+use super::SudokuSolver;
+
+use alloc::vec::Vec;
+
+/// A problem with the givens of a 9x9 grid, see `SudokuSolver::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// the value is not 0 (empty) or a digit from 1 to 9
+    OutOfRange { cell: Point<usize>, value: u8 },
+    /// two cells that share a row, column or region hold the same digit, `first` comes before `second` in row-major order
+    Duplicate { first: Point<usize>, second: Point<usize>, digit: u8 }
+}
+
+impl core::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Conflict::OutOfRange { cell, value } => write!(f, "{} contains {} which is not a digit from 1 to 9", cell.rc(), value),
+            Conflict::Duplicate { first, second, digit } => write!(f, "{} and {} both contain {}", first.rc(), second.rc(), digit)
+        }
+    }
+}
+
+impl SudokuSolver {
+    /// Every out of range value and every pair of givens that break the rules, in row-major order.
+    /// Unlike `new` this does not stop at the first problem, but it only finds direct conflicts
+    /// so a grid without any can still have no solution
+    pub fn validate(grid: &[[u8; 9]; 9]) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for cell in (0..81).map(|i| Point::from_index(i, 9)) {
+            let value = grid[cell.y][cell.x];
+            if value > 9 {
+                conflicts.push(Conflict::OutOfRange { cell, value });
+            } else if value != 0 {
+                conflicts.extend(SudokuSolver::peers(cell)
+                    .filter(|peer| peer.to_index(9) > cell.to_index(9) && grid[peer.y][peer.x] == value)
+                    .map(|peer| Conflict::Duplicate { first: cell, second: peer, digit: value }));
+            }
+        }
+        conflicts
+    }
+}
@@ -0,0 +1,77 @@
+use crate::point::Point;
+use super::{ErrorSudokuContainsAContradiction, SudokuIsUnsolvable, SudokuSolver};
+
+use alloc::vec::Vec;
+
+/// German whispers sudoku, neighbouring cells on a line differ by at least 5
+#[derive(Debug, Clone)]
+pub struct WhispersSudokuSolver {
+    solver: SudokuSolver,
+    lines: Vec<Vec<Point<usize>>>
+}
+
+impl WhispersSudokuSolver {
+    pub fn new(starting_state: [[u8; 9]; 9], lines: Vec<Vec<Point<usize>>>) -> Result<Self, ErrorSudokuContainsAContradiction> {
+        Ok(WhispersSudokuSolver {
+            solver: SudokuSolver::new(starting_state)?,
+            lines
+        })
+    }
+
+    pub fn solver(&self) -> &SudokuSolver {
+        &self.solver
+    }
+
+    pub fn solve(&mut self) -> Result<(), SudokuIsUnsolvable> {
+        let lines = self.lines.clone();
+        self.solver.solve_with_strategy(&mut |solver| apply_whispers_constraints(solver, &lines).map(|_| ()))
+    }
+
+    /// Removes candidates that are within 4 of every candidate of a neighbouring cell on the line,
+    /// returns how many candidates were removed
+    pub fn apply_whispers_constraints(&mut self) -> Result<usize, SudokuIsUnsolvable> {
+        apply_whispers_constraints(&mut self.solver, &self.lines).map_err(|_| SudokuIsUnsolvable)
+    }
+
+    pub fn check_if_correct(&self) -> bool {
+        self.solver.check_if_correct() && self.lines.iter().all(|line| {
+            line.windows(2).all(|pair| {
+                match (self.solver.get_cell(&pair[0]).value(), self.solver.get_cell(&pair[1]).value()) {
+                    (Some(first), Some(second)) => first.abs_diff(second) >= MIN_DIFFERENCE,
+                    _ => false
+                }
+            })
+        })
+    }
+}
+
+impl core::fmt::Display for WhispersSudokuSolver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.solver.fmt(f)
+    }
+}
+
+const MIN_DIFFERENCE: u8 = 5;
+
+fn apply_whispers_constraints(solver: &mut SudokuSolver, lines: &[Vec<Point<usize>>]) -> Result<usize, ()> {
+    let mut removed = 0;
+    for line in lines {
+        for pair in line.windows(2) {
+            removed += restrict_by_neighbour(solver, pair[0], pair[1])?;
+            removed += restrict_by_neighbour(solver, pair[1], pair[0])?;
+        }
+    }
+    Ok(removed)
+}
+
+fn restrict_by_neighbour(solver: &mut SudokuSolver, cell: Point<usize>, neighbour: Point<usize>) -> Result<usize, ()> {
+    let neighbour_candidates = solver.get_candidates(&neighbour);
+    let mut removed = 0;
+    for value in solver.get_candidates(&cell) {
+        if neighbour_candidates.iter().all(|other| value.abs_diff(*other) < MIN_DIFFERENCE) {
+            solver.get_cell_mut(&cell).remove(value)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,78 @@
+//! `wasm-bindgen` exports for a browser demo, behind the `wasm` feature. Build them with
+//! `cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features --features wasm --crate-type cdylib`
+//! and generate the JavaScript bindings with `wasm-bindgen --target web`.
+//!
+//! Boards cross the boundary as the 81 character single line format of `parse::from_line`, and nothing reads
+//! the clock or the system's randomness, which are not available in the browser.
+//! The tests in `tests/wasm.rs` run with `cargo test --target wasm32-unknown-unknown --no-default-features --features wasm`,
+//! which needs `wasm-bindgen-test-runner` of `wasm-bindgen-cli`
+
+use crate::parse;
+use crate::point::Point;
+use crate::sudoku::SudokuSolver;
+
+use alloc::string::{String, ToString};
+use js_sys::{Error, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+/// The solution of `board`, throws an `Error` with why it has none
+#[wasm_bindgen]
+pub fn solve(board: &str) -> Result<String, JsValue> {
+    let error = |e: &dyn ToString| JsValue::from(Error::new(&e.to_string()));
+    let starting_state = parse::from_line(board).map_err(|e| error(&e))?;
+    let solution = SudokuSolver::new(starting_state).map_err(|e| error(&e))?
+        .into_solution().map_err(|e| error(&e))?;
+    Ok(SudokuSolver::new(solution).map_err(|e| error(&e))?.to_flat_string())
+}
+
+/// The next digit to place as an object, e.g. `{ cell: "r1c2", value: 5, technique: "naked single" }`.
+/// When no technique forces a digit it is taken from the solution and `technique` is null,
+/// null when the board is full or has no solution
+#[wasm_bindgen]
+pub fn hint(board: &str) -> JsValue {
+    let Some((cell, value, technique)) = next_placement(board) else {
+        return JsValue::NULL;
+    };
+    let hint = Object::new();
+    for (key, value) in [("cell", JsValue::from(cell.rc().to_string())), ("value", JsValue::from(value)),
+                         ("technique", technique.map_or(JsValue::NULL, JsValue::from))] {
+        Reflect::set(&hint, &JsValue::from_str(key), &value).expect("setting a property of a new object");
+    }
+    hint.into()
+}
+
+/// the cell and digit of `hint` and the name of the technique that forces it, if one does
+fn next_placement(board: &str) -> Option<(Point<usize>, u8, Option<String>)> {
+    let solver = SudokuSolver::new(parse::from_line(board).ok()?).ok()?;
+    if let Some(placement) = solver.forced_placements().first() {
+        return Some((placement.cell, placement.value, Some(placement.technique.to_string())));
+    }
+    let solution = solver.clone().into_solution().ok()?;
+    let cell = (0..81).map(|i| Point::from_index(i, 9)).find(|point| !solver.is_given(*point))?;
+    Some((cell, solution[cell.y][cell.x], None))
+}
+
+/// A puzzle with a unique solution and at least `clues` givens, the same seed always gives the same puzzle.
+/// It starts from the minimal puzzle of `SudokuSolver::generate` and reveals cells of the solution
+/// until there are `clues` of them, so asking for fewer clues than that puzzle has still gives that puzzle
+#[wasm_bindgen]
+pub fn generate(seed: u32, clues: u8) -> String {
+    let mut puzzle = SudokuSolver::generate(seed as u64);
+    let solution = SudokuSolver::new(puzzle).ok()
+        .and_then(|solver| solver.into_solution().ok())
+        .expect("a generated puzzle has a solution");
+
+    let mut given = puzzle.iter().flatten().filter(|value| **value != 0).count();
+    // 37 and 81 have no common divisor so this visits every cell once, starting at a cell picked by the seed
+    for i in 0..81 {
+        if given >= clues as usize {
+            break;
+        }
+        let point = Point::from_index((seed as usize + i * 37) % 81, 9);
+        if puzzle[point.y][point.x] == 0 {
+            puzzle[point.y][point.x] = solution[point.y][point.x];
+            given += 1;
+        }
+    }
+    SudokuSolver::new(puzzle).expect("a subset of a solution cannot contain a contradiction").to_flat_string()
+}
@@ -0,0 +1,61 @@
+//! The `wasm` exports, run with `cargo test --target wasm32-unknown-unknown --no-default-features --features wasm`,
+//! which needs `wasm-bindgen-test-runner` of `wasm-bindgen-cli`
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use js_sys::{Error, Reflect};
+use sudoku_solver_cli::wasm;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const BOARD: &str = "000000080680470020019508647060900004342680000190050830000720403006005010003891500";
+
+fn get(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn solve_returns_the_solution() {
+    let solution = wasm::solve(BOARD).unwrap();
+    assert_eq!(solution.len(), 81);
+    assert!(solution.starts_with("734162985"));
+    assert!(BOARD.chars().zip(solution.chars()).all(|(given, digit)| given == '0' || given == digit));
+}
+
+#[wasm_bindgen_test]
+fn solve_throws_an_error() {
+    let error = wasm::solve("123").unwrap_err();
+    assert!(error.is_instance_of::<Error>());
+    assert!(!String::from(error.unchecked_into::<Error>().message()).is_empty());
+
+    let mut unsolvable = String::from(BOARD);
+    unsolvable.replace_range(0..1, "8");
+    assert!(wasm::solve(&unsolvable).is_err());
+}
+
+#[wasm_bindgen_test]
+fn hint_places_a_digit_of_the_solution() {
+    let hint = wasm::hint(BOARD);
+    let solution = wasm::solve(BOARD).unwrap();
+    let cell = get(&hint, "cell").as_string().unwrap();
+    let (row, column) = cell[1..].split_once('c').unwrap();
+    let index = (row.parse::<usize>().unwrap() - 1) * 9 + column.parse::<usize>().unwrap() - 1;
+    assert_eq!(BOARD.as_bytes()[index], b'0');
+    assert_eq!(get(&hint, "value").as_f64().unwrap() as u8, solution.as_bytes()[index] - b'0');
+    assert!(get(&hint, "technique").as_string().is_some());
+}
+
+#[wasm_bindgen_test]
+fn hint_is_null_without_a_next_digit() {
+    assert!(wasm::hint(&wasm::solve(BOARD).unwrap()).is_null());
+    assert!(wasm::hint("123").is_null());
+}
+
+#[wasm_bindgen_test]
+fn generate_is_deterministic() {
+    let puzzle = wasm::generate(7, 40);
+    assert_eq!(puzzle, wasm::generate(7, 40));
+    assert_eq!(puzzle.len(), 81);
+    assert!(puzzle.chars().filter(|c| *c != '0').count() >= 40);
+    assert!(wasm::solve(&puzzle).is_ok());
+}